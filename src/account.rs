@@ -1,62 +1,92 @@
-use anyhow::{ensure, Result};
-use hex;
 use sodiumoxide::crypto::sign;
 use sodiumoxide::crypto::sign::ed25519::{
     sign_detached, verify_detached, PublicKey, SecretKey, Seed, Signature,
 };
 use std::{convert::TryInto, fmt};
+use zeroize::Zeroizing;
 
-use crate::models::{BlockMessage, BlockType, ChainData, SignedMessage};
+use crate::{
+    error::{Error, Result},
+    keystore::{Keystore, KeystoreKind},
+    models::{BlockMessage, BlockType, ChainData, SignedMessage},
+};
 
-fn convert_vec_to_fixed_array<T, const N: usize>(vec: Vec<T>) -> [T; N] {
-    vec.try_into().unwrap_or_else(|v: Vec<T>| {
-        panic!(
-            "Expected a hex of length {} but it was {}",
-            N / 2,
-            v.len() / 2
-        )
-    })
-}
+/// Decodes a hex key, wiping the decoded bytes from memory when they are dropped
+fn hex_to_fixed_bytes<const N: usize>(hex_key: &str) -> Result<Zeroizing<[u8; N]>> {
+    let key_as_bytes = Zeroizing::new(hex::decode(hex_key)?);
+
+    if key_as_bytes.len() != N {
+        return Err(Error::InvalidKeyLength {
+            expected: N * 2,
+            found: key_as_bytes.len() * 2,
+        });
+    }
 
-fn hex_to_fixed_bytes<const N: usize>(hex_key: &str) -> [u8; N] {
-    let key_as_bytes = hex::decode(hex_key).ok().expect("Hex Key is invalid");
-    convert_vec_to_fixed_array::<u8, N>(key_as_bytes)
+    let mut fixed_bytes = Zeroizing::new([0; N]);
+    fixed_bytes.copy_from_slice(&key_as_bytes);
+
+    Ok(fixed_bytes)
 }
 
 /// An Account consists of an account number and a signing key.
 ///
 /// - The account number is used to identify an account on thenewboston network
 /// - The signing key is used to prove that you are the owner of the account by creating signatures and validating them with your account number.
-/// For this reason, your signing key should not be shared with anyone.
+///   For this reason, your signing key should not be shared with anyone.
+///
+/// The signing key is wiped from memory when the account is dropped and it is redacted from the
+/// account's `Debug` output. Use [`Account::reveal_secrets`] to print it.
 ///
 #[derive(PartialEq, Eq)]
 pub struct Account {
     signing_key_bytes: SecretKey,
     account_number_hex: String,
-    signing_key_hex: String,
 }
 
 impl fmt::Debug for Account {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Account")
             .field("Account Number", &self.account_number())
-            .field("Signing Key", &self.signing_key())
+            .field("Signing Key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Debug output of an account that includes its signing key
+struct RevealedAccount<'a>(&'a Account);
+
+impl fmt::Debug for RevealedAccount<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Account")
+            .field("Account Number", &self.0.account_number())
+            .field("Signing Key", &self.0.signing_key().as_str())
             .finish()
     }
 }
+
+impl Default for Account {
+    fn default() -> Self {
+        Account::new()
+    }
+}
+
 #[allow(dead_code)]
 impl Account {
     fn create(keypair: (PublicKey, SecretKey)) -> Self {
         let (pk, sk) = keypair;
-        let signing_key_as_bytes = sk.0;
 
         Account {
             signing_key_bytes: sk,
             account_number_hex: hex::encode(pk),
-            signing_key_hex: hex::encode(&signing_key_as_bytes[0..32]),
         }
     }
 
+    /// Creates an Account from the 32 bytes of a signing key
+    pub(crate) fn from_signing_key_bytes(signing_key: &[u8; 32]) -> Self {
+        let priv_key_as_seed = Seed(*signing_key);
+        Account::create(sign::keypair_from_seed(&priv_key_as_seed))
+    }
+
     /// Creates an random Account
     ///
     /// ```
@@ -65,7 +95,7 @@ impl Account {
     ///     let acc = Account::new();
     ///
     ///     // store you signing key safely before you proceed
-    ///     println!("siging key: {}", acc.signing_key());
+    ///     println!("siging key: {}", acc.signing_key().as_str());
     ///
     /// ```
     pub fn new() -> Self {
@@ -81,25 +111,60 @@ impl Account {
     ///     let signing_key = "8cf08eb96b00b5a4df86a750bb7ae595a9dbbe91fc091463bfb3d950d5dac467";
     ///     let acc = Account::from_signing_key(signing_key).unwrap();
     ///
-    ///     assert_eq!(acc.signing_key(), signing_key);
+    ///     assert_eq!(acc.signing_key().as_str(), signing_key);
     ///
     /// ```
     pub fn from_signing_key(signing_key_hex: &str) -> Result<Self> {
-        let result = hex::decode(signing_key_hex);
-        ensure!(result.is_ok(), result.err().unwrap());
-
-        let signing_key_as_bytes = result.unwrap();
-        let result = Seed::from_slice(&signing_key_as_bytes);
-        ensure!(
-            result.is_some(),
-            format!(
-                "Signing Key hex needs to be of length 64 but only found {}",
-                signing_key_as_bytes.len() * 2
-            )
-        );
-        let priv_key_as_seed = result.unwrap();
-        let keypair = sign::keypair_from_seed(&priv_key_as_seed);
-        Ok(Account::create(keypair))
+        let signing_key_as_bytes = hex_to_fixed_bytes::<32>(signing_key_hex)?;
+        Ok(Account::from_signing_key_bytes(&signing_key_as_bytes))
+    }
+
+    /// Encrypts the signing key with a password and returns the keystore as a json string
+    ///
+    /// The account number is stored unencrypted in the keystore so the file can be identified without the password
+    ///
+    /// # Example
+    /// ```
+    ///     use tnb_rs::Account;
+    ///
+    ///     let acc = Account::new();
+    ///     let keystore = acc.to_keystore("password").unwrap();
+    ///
+    ///     // save the keystore to a file
+    ///
+    ///     let restored_acc = Account::from_keystore(&keystore, "password").unwrap();
+    ///     assert_eq!(acc, restored_acc);
+    ///
+    ///     assert!(Account::from_keystore(&keystore, "wrong password").is_err());
+    /// ```
+    pub fn to_keystore(&self, password: &str) -> Result<String> {
+        Keystore::encrypt(
+            KeystoreKind::Account,
+            Some(self.account_number()),
+            &self.signing_key_bytes.0[..32],
+            password,
+        )?
+        .to_json()
+    }
+
+    /// Decrypts an Account from a keystore created with [`Account::to_keystore`]
+    pub fn from_keystore(keystore_json: &str, password: &str) -> Result<Self> {
+        let keystore = Keystore::from_json(keystore_json, KeystoreKind::Account)?;
+        let signing_key = keystore.decrypt(password)?;
+
+        let signing_key: &[u8; 32] = signing_key.as_slice().try_into().map_err(|_| {
+            Error::InvalidKeystore("The signing key has to be 32 bytes long".to_string())
+        })?;
+        let account = Account::from_signing_key_bytes(signing_key);
+
+        match keystore.account_number() {
+            Some(account_number) if account_number != account.account_number() => {
+                Err(Error::InvalidKeystore(
+                    "The account number does not match the signing key".to_string(),
+                ))
+            }
+            _ => Ok(account),
+        }
     }
 
     /// Returns the account number as a hex string
@@ -108,15 +173,32 @@ impl Account {
     }
 
     /// Returns the signing key as a hex string
-    pub fn signing_key(&self) -> &str {
-        &self.signing_key_hex
+    ///
+    /// The hex string is created on each call and wiped from memory when it is dropped
+    pub fn signing_key(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(&self.signing_key_bytes.0[..32]))
     }
 
     /// Returns a tuple of the account number and signing key
-    pub fn keypair_as_hex(&self) -> (&str, &str) {
+    pub fn keypair_as_hex(&self) -> (&str, Zeroizing<String>) {
         (self.account_number(), self.signing_key())
     }
 
+    /// Returns a value whose `Debug` output includes the signing key
+    ///
+    /// # Example
+    /// ```
+    ///     use tnb_rs::Account;
+    ///
+    ///     let acc = Account::new();
+    ///
+    ///     assert!(!format!("{:?}", acc).contains(acc.signing_key().as_str()));
+    ///     assert!(format!("{:?}", acc.reveal_secrets()).contains(acc.signing_key().as_str()));
+    /// ```
+    pub fn reveal_secrets(&self) -> impl fmt::Debug + '_ {
+        RevealedAccount(self)
+    }
+
     /// Checks if a signing key and account number are keypairs
     ///
     /// # Example
@@ -128,17 +210,17 @@ impl Account {
     ///     let acc = Account::from_signing_key(signing_key).unwrap();
     ///     let (acc_num, sk ) = acc.keypair_as_hex();
     ///     
-    ///     assert_eq!(signing_key, sk);
+    ///     assert_eq!(signing_key, sk.as_str());
     ///
-    ///     let is_keypair = Account::is_valid_keypair(&sk, &acc_num);
+    ///     let is_keypair = Account::is_valid_keypair(&sk, &acc_num).unwrap();
     ///     assert_eq!(is_keypair, true);
     ///     
     /// ```
     ///
-    pub fn is_valid_keypair(_signing_key_hex: &str, _account_number_hex: &str) -> bool {
-        let acc = Account::from_signing_key(_signing_key_hex).unwrap();
+    pub fn is_valid_keypair(_signing_key_hex: &str, _account_number_hex: &str) -> Result<bool> {
+        let acc = Account::from_signing_key(_signing_key_hex)?;
 
-        acc.account_number().eq(_account_number_hex)
+        Ok(acc.account_number().eq(_account_number_hex))
     }
 
     /// Signs the given message with the Account's signing key
@@ -161,41 +243,40 @@ impl Account {
     /// - the signer's `account number`
     ///
     /// # Returns
-    /// A `signature` in form of a hex string
+    /// `true` if the signature is valid. An error is returned if the signature or account number is not a valid hex key
     ///
-
     pub fn verify_signature(
         signature_as_hex: &str,
         message: &str,
         account_number_hex: &str,
-    ) -> bool {
-        let signature = hex_to_fixed_bytes::<64>(signature_as_hex);
-        let account_number = hex_to_fixed_bytes::<32>(account_number_hex);
+    ) -> Result<bool> {
+        let signature = hex_to_fixed_bytes::<64>(signature_as_hex)?;
+        let account_number = hex_to_fixed_bytes::<32>(account_number_hex)?;
 
-        verify_detached(
-            &Signature::new(signature),
+        Ok(verify_detached(
+            &Signature::from(*signature),
             message.as_bytes(),
-            &PublicKey(account_number),
-        )
+            &PublicKey(*account_number),
+        ))
     }
 
     /// Creates a block message that can be broadcasted to make changes to an account on the network
-    pub fn create_block_message<'a>(&'a self, data: &'a BlockType) -> BlockMessage {
-        let serialized_block = serde_json::to_string(&data);
-        BlockMessage {
-            account_number: self.account_number(),
-            message: data,
-            signature: self.create_signature(&serialized_block.unwrap()),
-        }
+    pub fn create_block_message(&self, data: &BlockType) -> Result<BlockMessage> {
+        let serialized_block = serde_json::to_string(&data)?;
+        Ok(BlockMessage {
+            account_number: self.account_number().to_string(),
+            message: data.clone(),
+            signature: self.create_signature(&serialized_block),
+        })
     }
 
     /// Creates a message that nodes can broadcast to make changes on the network
-    pub fn create_signed_message<'a>(&'a self, data: &'a ChainData) -> SignedMessage {
-        let serialized_data = serde_json::to_string(&data);
-        SignedMessage {
-            message: data,
-            node_identifier: self.account_number(),
-            signature: self.create_signature(&serialized_data.unwrap()),
-        }
+    pub fn create_signed_message(&self, data: &ChainData) -> Result<SignedMessage> {
+        let serialized_data = serde_json::to_string(&data)?;
+        Ok(SignedMessage {
+            message: data.clone(),
+            node_identifier: self.account_number().to_string(),
+            signature: self.create_signature(&serialized_data),
+        })
     }
 }
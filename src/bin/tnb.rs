@@ -0,0 +1,633 @@
+//! Command line wallet for [thenewboston](https://thenewboston.com/) network
+//!
+//! Run `tnb --help` to list the commands. With the `--json` flag every command prints a single
+//! JSON object to stdout, and errors are printed to stderr as `{"error": "..."}`.
+//!
+//! Secrets can be passed in environment variables instead of flags so they don't end up in the
+//! shell history:
+//! - `TNB_SIGNING_KEY` for `--signing-key`
+//! - `TNB_MNEMONIC` and `TNB_MNEMONIC_PASSWORD` for `--mnemonic` and `--password`
+//! - `TNB_NODE` for `--node`
+
+use std::{fmt, path::PathBuf, process::ExitCode};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use serde_json::json;
+
+use tnb_rs::{
+    models::{
+        NodeType, PaginatedQueryTrait, Transaction, TransactionQueryBuilder, TrustEntry,
+        TrustTable, TrustUpdate,
+    },
+    nodes::RegularNode,
+    responses::{BlockResponse, PaginatedResponse, TransactionResponse},
+    Account, Client, Error, HDWallet, Result, Wallet,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "tnb",
+    version,
+    about = "Command line wallet for thenewboston network"
+)]
+struct Cli {
+    /// Print the output as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a random account or HD wallet
+    Keygen {
+        /// Generate a HD wallet with a mnemonic phrase and print its first account
+        #[arg(long)]
+        mnemonic: bool,
+    },
+
+    /// Restore a HD wallet from its mnemonic phrase and print its seed and first account
+    Restore(MnemonicArgs),
+
+    /// Derive an account from a HD wallet
+    Derive {
+        #[command(flatten)]
+        wallet: MnemonicArgs,
+
+        /// Index of the account in the bip44 path
+        #[arg(long, default_value_t = 0)]
+        account_index: u32,
+
+        /// Index of the address in the bip44 path
+        #[arg(long, default_value_t = 0)]
+        address_index: u32,
+    },
+
+    /// Get the number of coins held by an account
+    Balance {
+        /// The account to look up
+        account_number: String,
+
+        #[command(flatten)]
+        node: NodeArgs,
+    },
+
+    /// Send coins to a single recipient
+    Send {
+        #[command(flatten)]
+        signer: SignerArgs,
+
+        #[command(flatten)]
+        node: NodeArgs,
+
+        /// The account receiving the coins
+        recipient: String,
+
+        /// The number of coins to send
+        amount: u64,
+
+        /// Optional memo stored with the transaction
+        #[arg(long)]
+        memo: Option<String>,
+    },
+
+    /// Send coins to multiple recipients in a single block
+    SendMany {
+        #[command(flatten)]
+        signer: SignerArgs,
+
+        #[command(flatten)]
+        node: NodeArgs,
+
+        /// A transaction to include in the block
+        #[arg(
+            long = "tx",
+            value_name = "RECIPIENT:AMOUNT[:MEMO]",
+            required = true,
+            value_parser = parse_transaction
+        )]
+        txs: Vec<Transaction>,
+    },
+
+    /// List the transactions stored by a bank
+    Transactions {
+        #[command(flatten)]
+        node: NodeArgs,
+
+        #[command(flatten)]
+        filters: TransactionFilters,
+    },
+
+    /// Apply a trust table to a bank
+    ///
+    /// The table is a json array of `{"type", "identifier", "trust"}` entries, where the type is
+    /// `account`, `bank` or `validator`. The requests are signed with the bank's node identifier
+    /// key, passed as the signing key. Exits with a non-zero status if any entry failed.
+    Trust {
+        #[command(flatten)]
+        signer: SignerArgs,
+
+        #[command(flatten)]
+        node: NodeArgs,
+
+        /// Path of the trust table
+        table: PathBuf,
+    },
+
+    /// Sign a message with an account's signing key
+    Sign {
+        #[command(flatten)]
+        signer: SignerArgs,
+
+        /// The message to sign
+        message: String,
+    },
+
+    /// Verify that a message was signed by an account
+    ///
+    /// Exits with a non-zero status if the signature is invalid
+    Verify {
+        /// The account that signed the message
+        account_number: String,
+
+        /// The signature in hex format
+        signature: String,
+
+        /// The signed message
+        message: String,
+    },
+}
+
+#[derive(Args)]
+struct MnemonicArgs {
+    /// The HD wallet's mnemonic phrase
+    #[arg(long, env = "TNB_MNEMONIC", hide_env_values = true)]
+    mnemonic: String,
+
+    /// Optional bip39 password used with the mnemonic phrase
+    #[arg(long, env = "TNB_MNEMONIC_PASSWORD", hide_env_values = true)]
+    password: Option<String>,
+}
+
+impl MnemonicArgs {
+    fn wallet(&self) -> Result<HDWallet> {
+        HDWallet::from_mnemonic(&self.mnemonic, self.password.as_deref())
+    }
+}
+
+#[derive(Args)]
+struct SignerArgs {
+    /// The signing key of the sender in hex format
+    #[arg(long, env = "TNB_SIGNING_KEY", hide_env_values = true)]
+    signing_key: String,
+}
+
+impl SignerArgs {
+    fn account(&self) -> Result<Account> {
+        Account::from_signing_key(&self.signing_key)
+    }
+}
+
+#[derive(Args)]
+struct NodeArgs {
+    /// Url of the bank to connect to
+    #[arg(long, env = "TNB_NODE")]
+    node: String,
+}
+
+#[derive(Args)]
+struct TransactionFilters {
+    /// Only list transactions sent or received by the account
+    #[arg(long)]
+    account_number: Option<String>,
+
+    /// Only list transactions sent by the account
+    #[arg(long)]
+    sender: Option<String>,
+
+    /// Only list transactions received by the account
+    #[arg(long)]
+    recipient: Option<String>,
+
+    /// Only list transactions in the block with the balance key
+    #[arg(long)]
+    balance_key: Option<String>,
+
+    /// Only list the transaction with the id
+    #[arg(long)]
+    id: Option<String>,
+
+    /// Only list the fees paid to a type of node, or the transactions that are not fees
+    #[arg(long, value_enum)]
+    fee: Option<FeeFilter>,
+
+    /// Order the transactions by a field, prefixed with `-` for descending order
+    #[arg(long)]
+    ordering: Option<String>,
+
+    /// The max number of transactions to list
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    limit: Option<u8>,
+
+    /// The number of transactions to skip
+    #[arg(long)]
+    offset: Option<u8>,
+}
+
+impl TransactionFilters {
+    fn query(&self) -> TransactionQueryBuilder<'_> {
+        let mut query = TransactionQueryBuilder::new();
+
+        if let Some(account_number) = &self.account_number {
+            query.account_number(account_number);
+        }
+        if let Some(sender) = &self.sender {
+            query.sender(sender);
+        }
+        if let Some(recipient) = &self.recipient {
+            query.recipient(recipient);
+        }
+        if let Some(balance_key) = &self.balance_key {
+            query.balance_key(balance_key);
+        }
+        if let Some(id) = &self.id {
+            query.id(id);
+        }
+        if let Some(fee) = self.fee {
+            query.fee(Some(fee.into()));
+        }
+        if let Some(ordering) = &self.ordering {
+            query.ordering(ordering);
+        }
+        if let Some(limit) = self.limit {
+            query.limit(limit);
+        }
+        if let Some(offset) = self.offset {
+            query.offset(offset);
+        }
+
+        query
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FeeFilter {
+    Bank,
+    PrimaryValidator,
+    None,
+}
+
+impl From<FeeFilter> for NodeType {
+    fn from(fee: FeeFilter) -> Self {
+        match fee {
+            FeeFilter::Bank => NodeType::BANK,
+            FeeFilter::PrimaryValidator => NodeType::PRIMARY_VALIDATOR,
+            FeeFilter::None => NodeType::NONE,
+        }
+    }
+}
+
+/// Parses a transaction in the `RECIPIENT:AMOUNT[:MEMO]` format
+fn parse_transaction(value: &str) -> std::result::Result<Transaction, String> {
+    let mut parts = value.splitn(3, ':');
+
+    let recipient = parts.next().unwrap_or_default();
+    let amount = parts
+        .next()
+        .ok_or_else(|| format!("expected RECIPIENT:AMOUNT[:MEMO] but found '{}'", value))?
+        .parse::<u64>()
+        .map_err(|e| format!("invalid amount in '{}': {}", value, e))?;
+
+    Ok(match parts.next() {
+        Some(memo) => Transaction::new_with_memo(recipient, amount, memo),
+        None => Transaction::new(recipient, amount),
+    })
+}
+
+/// The result of a command
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Output {
+    Account {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mnemonic: Option<String>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        seed: Option<String>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        account_index: Option<u32>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        address_index: Option<u32>,
+
+        account_number: String,
+        signing_key: String,
+    },
+    Balance {
+        account_number: String,
+        balance: Option<u64>,
+    },
+    Block(BlockResponse),
+    Transactions(PaginatedResponse<TransactionResponse>),
+    Trust {
+        updates: Vec<TrustOutput>,
+    },
+    Signature {
+        account_number: String,
+        message: String,
+        signature: String,
+    },
+    Verification {
+        valid: bool,
+    },
+}
+
+/// The result of one entry of a trust table
+#[derive(Serialize)]
+struct TrustOutput {
+    #[serde(flatten)]
+    entry: TrustEntry,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stored_trust: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl From<TrustUpdate> for TrustOutput {
+    fn from(update: TrustUpdate) -> Self {
+        let (stored_trust, error) = match update.result {
+            Ok(trust) => (trust, None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        TrustOutput {
+            entry: update.entry,
+            stored_trust,
+            error,
+        }
+    }
+}
+
+impl Output {
+    fn account(acc: &Account) -> Self {
+        Output::Account {
+            mnemonic: None,
+            seed: None,
+            account_index: None,
+            address_index: None,
+            account_number: acc.account_number().to_string(),
+            signing_key: acc.signing_key().to_string(),
+        }
+    }
+
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Output::Verification { valid: false } => ExitCode::FAILURE,
+            Output::Trust { updates } if updates.iter().any(|u| u.error.is_some()) => {
+                ExitCode::FAILURE
+            }
+            _ => ExitCode::SUCCESS,
+        }
+    }
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Output::Account {
+                mnemonic,
+                seed,
+                account_index,
+                address_index,
+                account_number,
+                signing_key,
+            } => {
+                if let Some(mnemonic) = mnemonic {
+                    writeln!(f, "mnemonic:       {}", mnemonic)?;
+                }
+                if let Some(seed) = seed {
+                    writeln!(f, "seed:           {}", seed)?;
+                }
+                if let Some(account_index) = account_index {
+                    writeln!(f, "account index:  {}", account_index)?;
+                }
+                if let Some(address_index) = address_index {
+                    writeln!(f, "address index:  {}", address_index)?;
+                }
+                writeln!(f, "account number: {}", account_number)?;
+                write!(f, "signing key:    {}", signing_key)
+            }
+            Output::Balance {
+                account_number,
+                balance,
+            } => match balance {
+                Some(balance) => write!(f, "{}: {}", account_number, balance),
+                None => write!(f, "{}: account has never been funded", account_number),
+            },
+            Output::Block(block) => {
+                writeln!(f, "block:       {}", block.id)?;
+                writeln!(f, "sender:      {}", block.sender)?;
+                writeln!(f, "balance key: {}", block.balance_key)?;
+                write!(f, "signature:   {}", block.signature)
+            }
+            Output::Transactions(page) => {
+                writeln!(f, "{} transactions", page.count)?;
+                for tx in &page.results {
+                    write!(
+                        f,
+                        "\n{}  {} -> {}  {}",
+                        tx.id, tx.block.sender, tx.recipient, tx.amount
+                    )?;
+                    if let Some(fee) = &tx.fee {
+                        write!(f, "  fee: {}", fee)?;
+                    }
+                    if let Some(memo) = tx.memo.as_deref().filter(|memo| !memo.is_empty()) {
+                        write!(f, "  memo: {}", memo)?;
+                    }
+                }
+                Ok(())
+            }
+            Output::Trust { updates } => {
+                for (i, update) in updates.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{} {}: ", update.entry.target, update.entry.identifier)?;
+                    match (&update.stored_trust, &update.error) {
+                        (_, Some(e)) => write!(f, "error: {}", e)?,
+                        (Some(trust), None) => write!(f, "{}", trust)?,
+                        (None, None) => {}
+                    }
+                }
+                Ok(())
+            }
+            Output::Signature { signature, .. } => write!(f, "{}", signature),
+            Output::Verification { valid } => {
+                write!(f, "{}", if *valid { "valid" } else { "invalid" })
+            }
+        }
+    }
+}
+
+async fn run(command: Command) -> Result<Output> {
+    match command {
+        Command::Keygen { mnemonic: false } => Ok(Output::account(&Account::new())),
+
+        Command::Keygen { mnemonic: true } => {
+            let hd = HDWallet::new();
+            wallet_output(&hd, 0, 0, true, false)
+        }
+
+        Command::Restore(args) => wallet_output(&args.wallet()?, 0, 0, true, true),
+
+        Command::Derive {
+            wallet,
+            account_index,
+            address_index,
+        } => wallet_output(
+            &wallet.wallet()?,
+            account_index,
+            address_index,
+            false,
+            false,
+        ),
+
+        Command::Balance {
+            account_number,
+            node,
+        } => {
+            let mut client = Client::new(&node.node)?;
+            client.update_config().await?;
+
+            let balance = client.get_account_balance(&account_number).await?;
+
+            Ok(Output::Balance {
+                account_number,
+                balance,
+            })
+        }
+
+        Command::Send {
+            signer,
+            node,
+            recipient,
+            amount,
+            memo,
+        } => {
+            let tx = match memo {
+                Some(memo) => Transaction::new_with_memo(&recipient, amount, &memo),
+                None => Transaction::new(&recipient, amount),
+            };
+
+            send(&signer, &node, &[tx]).await
+        }
+
+        Command::SendMany { signer, node, txs } => send(&signer, &node, &txs).await,
+
+        Command::Transactions { node, filters } => {
+            let node = RegularNode::new(&node.node)?;
+            let page = node.get_transactions(Some(&filters.query())).await?;
+
+            Ok(Output::Transactions(page))
+        }
+
+        Command::Trust {
+            signer,
+            node,
+            table,
+        } => {
+            let table = TrustTable::from_file(table)?;
+            let node = RegularNode::new(&node.node)?;
+
+            let updates = node.apply_trust_table(&signer.account()?, &table).await;
+
+            Ok(Output::Trust {
+                updates: updates.into_iter().map(TrustOutput::from).collect(),
+            })
+        }
+
+        Command::Sign { signer, message } => {
+            let acc = signer.account()?;
+
+            Ok(Output::Signature {
+                account_number: acc.account_number().to_string(),
+                signature: acc.create_signature(&message),
+                message,
+            })
+        }
+
+        Command::Verify {
+            account_number,
+            signature,
+            message,
+        } => Ok(Output::Verification {
+            valid: Account::verify_signature(&signature, &message, &account_number)?,
+        }),
+    }
+}
+
+fn wallet_output(
+    hd: &HDWallet,
+    account_index: u32,
+    address_index: u32,
+    with_mnemonic: bool,
+    with_seed: bool,
+) -> Result<Output> {
+    let acc = hd.get_account(account_index, address_index)?;
+
+    Ok(Output::Account {
+        mnemonic: with_mnemonic.then(|| hd.mnemonic().to_string()),
+        seed: with_seed.then(|| hd.seed_hex().to_string()),
+        account_index: Some(account_index),
+        address_index: Some(address_index),
+        account_number: acc.account_number().to_string(),
+        signing_key: acc.signing_key().to_string(),
+    })
+}
+
+async fn send(signer: &SignerArgs, node: &NodeArgs, txs: &[Transaction]) -> Result<Output> {
+    let acc = signer.account()?;
+
+    let mut wallet = Wallet::new(&acc, &node.node)?;
+    wallet.init().await?;
+
+    Ok(Output::Block(wallet.send_transactions(txs).await?))
+}
+
+fn print_error(e: &Error, json: bool) {
+    if json {
+        eprintln!("{}", json!({ "error": e.to_string() }));
+    } else {
+        eprintln!("error: {}", e);
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli.command).await {
+        Ok(output) => {
+            if cli.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&output).expect("outputs can be serialized")
+                );
+            } else {
+                println!("{}", output);
+            }
+
+            output.exit_code()
+        }
+        Err(e) => {
+            print_error(&e, cli.json);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use tokio::runtime::Runtime;
+
+use crate::{
+    account::Account,
+    blocking::new_runtime,
+    client::{self, BlockStatus},
+    error::Result,
+    models::Transaction,
+    node_pool::NodePool,
+    responses::BlockResponse,
+};
+
+/// Blocking version of [`crate::Client`]
+pub struct Client {
+    inner: client::Client,
+    runtime: Runtime,
+}
+
+impl Client {
+    /// Create a new Client
+    pub fn new(node_url: &str) -> Result<Self> {
+        Ok(Client {
+            inner: client::Client::new(node_url)?,
+            runtime: new_runtime(),
+        })
+    }
+
+    /// Create a new Client that fails over between the nodes in the pool
+    pub fn with_pool(pool: NodePool) -> Result<Self> {
+        Ok(Client {
+            inner: client::Client::with_pool(pool)?,
+            runtime: new_runtime(),
+        })
+    }
+
+    /// The pool of nodes the client fails over between
+    pub fn pool(&self) -> Option<&NodePool> {
+        self.inner.pool()
+    }
+
+    /// Returns a reference to the wrapped async client
+    pub fn get_ref(&self) -> &client::Client {
+        &self.inner
+    }
+
+    /// Returns the wrapped async client
+    pub fn into_inner(self) -> client::Client {
+        self.inner
+    }
+
+    /// Rerieve the latest config details for your node and it's selected primary validator
+    pub fn update_config(&mut self) -> Result<()> {
+        self.runtime.block_on(self.inner.update_config())
+    }
+
+    /// Forgets the cached primary validator, so it is resolved through the node again on the next
+    /// [`update_config`](Self::update_config)
+    pub fn invalidate_primary_validator(&mut self) {
+        self.inner.invalidate_primary_validator()
+    }
+
+    /// Retrieves the number of coins held by an account
+    pub fn get_account_balance(&self, account_number: &str) -> Result<Option<u64>> {
+        self.runtime
+            .block_on(self.inner.get_account_balance(account_number))
+    }
+
+    /// Send multiple transactions
+    pub fn send_transactions(
+        &self,
+        sender: &Account,
+        txs: &[Transaction],
+    ) -> Result<BlockResponse> {
+        self.runtime
+            .block_on(self.inner.send_transactions(sender, txs))
+    }
+
+    /// Send a single transaction
+    pub fn send_transaction(&self, sender: &Account, tx: &Transaction) -> Result<BlockResponse> {
+        self.runtime
+            .block_on(self.inner.send_transaction(sender, tx))
+    }
+
+    /// Checks whether a block has been confirmed or rejected by the validators
+    pub fn block_status(&self, block_id_or_balance_key: &str) -> Result<BlockStatus> {
+        self.runtime
+            .block_on(self.inner.block_status(block_id_or_balance_key))
+    }
+}
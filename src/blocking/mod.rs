@@ -0,0 +1,17 @@
+mod client;
+mod nodes;
+mod wallet;
+
+pub use client::*;
+pub use nodes::*;
+pub use wallet::*;
+
+use tokio::runtime::{Builder, Runtime};
+
+/// Creates the runtime that drives the async api for the blocking wrappers
+fn new_runtime() -> Runtime {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to start the tokio runtime for the blocking api")
+}
@@ -0,0 +1,494 @@
+use std::sync::Arc;
+
+use futures_util::stream::{BoxStream, StreamExt};
+use serde::de;
+use tokio::runtime::Runtime;
+
+use crate::{
+    account::Account,
+    blocking::new_runtime,
+    discovery::DiscoveredAccount,
+    error::Result,
+    hd_wallet::HDWallet,
+    models::{
+        AccountQueryBuilder, BankQueryBuilder, BlockMessage, BlockQueryBuilder, BlockType,
+        ConfirmationBlockQueryBuilder, InvalidBlockQueryBuilder, MaintenanceCommand,
+        PaginationOptions, SearchParams, TransactionQueryBuilder, TrustTable, TrustUpdate,
+        ValidatorQueryBuilder,
+    },
+    nodes::{self, ClientConfig, ServerNodeTrait, ValidatorTrait},
+    responses::{
+        AccountBalanceLockResponse, AccountBalanceResponse, AccountResponse, BankResponse,
+        BlockResponse, CleanResponse, ConfigResponse, ConfirmationBlockResponse,
+        ConnectionRequestResult, CrawlResponse, InvalidBlockResponse, PaginatedResponse,
+        TransactionResponse, ValidatorConfirmationBlockResponse, ValidatorResponse,
+    },
+    transport::Transport,
+};
+
+/// A blocking wrapper around one of the async node types
+///
+/// Each wrapper drives the async node on its own runtime, so it must not be used from inside an async context.
+#[derive(Debug)]
+pub struct Node<N> {
+    inner: N,
+    runtime: Arc<Runtime>,
+}
+
+/// Blocking version of [`nodes::RegularNode`]
+pub type RegularNode = Node<nodes::RegularNode>;
+
+/// Blocking version of [`nodes::PrimaryValidator`]
+pub type PrimaryValidator = Node<nodes::PrimaryValidator>;
+
+/// Blocking version of [`nodes::ConfirmationValidator`]
+pub type ConfirmationValidator = Node<nodes::ConfirmationValidator>;
+
+impl<N> Node<N> {
+    /// Wraps an async node so it can be used without a runtime
+    pub fn from_async(inner: N) -> Self {
+        Node {
+            inner,
+            runtime: Arc::new(new_runtime()),
+        }
+    }
+
+    /// Returns a reference to the wrapped async node
+    pub fn get_ref(&self) -> &N {
+        &self.inner
+    }
+
+    /// Returns the wrapped async node
+    pub fn into_inner(self) -> N {
+        self.inner
+    }
+}
+
+impl<N: ServerNodeTrait + Sync> Node<N> {
+    /// Retrieve the next set of paginated data
+    pub fn next_page<T: de::DeserializeOwned>(
+        &self,
+        page: &PaginatedResponse<T>,
+    ) -> Result<PaginatedResponse<T>> {
+        self.runtime.block_on(self.inner.get_base().next_page(page))
+    }
+
+    /// Retrieve the previous set of paginated data
+    pub fn prev_page<T: de::DeserializeOwned>(
+        &self,
+        page: &PaginatedResponse<T>,
+    ) -> Result<PaginatedResponse<T>> {
+        self.runtime.block_on(self.inner.get_base().prev_page(page))
+    }
+
+    /// Get the config details of a node
+    pub fn get_config(&self) -> Result<ConfigResponse> {
+        self.runtime.block_on(self.inner.get_config())
+    }
+
+    /// Asks another node to connect to this node
+    pub fn send_connection_request(
+        &self,
+        node_signer: &Account,
+        target: &str,
+    ) -> Result<ConnectionRequestResult> {
+        self.runtime
+            .block_on(self.inner.send_connection_request(node_signer, target))
+    }
+
+    /// Get the status of the node's crawl of the network
+    pub fn get_crawl_status(&self) -> Result<CrawlResponse> {
+        self.runtime
+            .block_on(self.inner.get_base().get_crawl_status())
+    }
+
+    /// Starts or stops the node's crawl of the network
+    pub fn send_crawl_command(
+        &self,
+        node_signer: &Account,
+        command: MaintenanceCommand,
+    ) -> Result<CrawlResponse> {
+        self.runtime.block_on(
+            self.inner
+                .get_base()
+                .send_crawl_command(node_signer, command),
+        )
+    }
+
+    /// Get the status of the node's clean of its stale banks and validators
+    pub fn get_clean_status(&self) -> Result<CleanResponse> {
+        self.runtime
+            .block_on(self.inner.get_base().get_clean_status())
+    }
+
+    /// Starts or stops the node's clean of the banks and validators that are no longer online
+    pub fn send_clean_command(
+        &self,
+        node_signer: &Account,
+        command: MaintenanceCommand,
+    ) -> Result<CleanResponse> {
+        self.runtime.block_on(
+            self.inner
+                .get_base()
+                .send_clean_command(node_signer, command),
+        )
+    }
+
+    /// Lazily retrieves every item of a paginated endpoint
+    pub fn paginated_iter<T>(
+        &self,
+        endpoint: &str,
+        query: Option<SearchParams<'_>>,
+        options: PaginationOptions,
+    ) -> PaginatedIter<'_, T>
+    where
+        T: de::DeserializeOwned + Send + 'static,
+    {
+        PaginatedIter {
+            stream: self
+                .inner
+                .get_base()
+                .paginated_stream(endpoint, query, options),
+            runtime: &self.runtime,
+        }
+    }
+}
+
+/// Blocking iterator over every item of a paginated endpoint
+///
+/// Each page is requested when the items of the previous page have been consumed
+pub struct PaginatedIter<'a, T> {
+    stream: BoxStream<'a, Result<T>>,
+    runtime: &'a Runtime,
+}
+
+impl<T> Iterator for PaginatedIter<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl<N: ValidatorTrait + Sync> Node<N> {
+    /// Retrieve the number of coins in an account
+    pub fn get_account_balance(&self, account_number: &str) -> Result<AccountBalanceResponse> {
+        self.runtime
+            .block_on(self.inner.get_account_balance(account_number))
+    }
+
+    /// Get the balance lock of an account's next transaction
+    pub fn get_account_balance_lock(
+        &self,
+        account_number: &str,
+    ) -> Result<AccountBalanceLockResponse> {
+        self.runtime
+            .block_on(self.inner.get_account_balance_lock(account_number))
+    }
+
+    /// Get the confirmation block the validator created for a block
+    pub fn get_confirmation_block_by_block_id(
+        &self,
+        block_identifier: &str,
+    ) -> Result<ValidatorConfirmationBlockResponse> {
+        self.runtime.block_on(
+            self.inner
+                .get_confirmation_block_by_block_id(block_identifier),
+        )
+    }
+
+    /// Get the accounts this validator knows about
+    pub fn get_accounts(
+        &self,
+        query: Option<&AccountQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<AccountResponse>> {
+        self.runtime.block_on(self.inner.get_accounts(query))
+    }
+
+    /// Get the banks this validator knows about
+    pub fn get_banks(
+        &self,
+        query: Option<&BankQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<BankResponse>> {
+        self.runtime.block_on(self.inner.get_banks(query))
+    }
+
+    /// Get the validators this validator knows about
+    pub fn get_validators(
+        &self,
+        query: Option<&ValidatorQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<ValidatorResponse>> {
+        self.runtime.block_on(self.inner.get_validators(query))
+    }
+}
+
+impl RegularNode {
+    /// Create a new instance that connects to a regular node's url
+    ///
+    /// ```
+    ///     use tnb_rs::blocking::RegularNode;
+    ///
+    ///     let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///
+    /// ```
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Node::from_async(nodes::RegularNode::new(url)?))
+    }
+
+    /// Create a new instance that sends requests with the config's settings
+    pub fn with_config(url: &str, config: &ClientConfig) -> Result<Self> {
+        Ok(Node::from_async(nodes::RegularNode::with_config(
+            url, config,
+        )?))
+    }
+
+    /// Create a new instance that sends requests through the transport
+    pub fn with_transport(url: &str, transport: Arc<dyn Transport>) -> Result<Self> {
+        Ok(Node::from_async(nodes::RegularNode::with_transport(
+            url, transport,
+        )?))
+    }
+
+    /// Get transactions on the network
+    pub fn get_transactions(
+        &self,
+        query: Option<&TransactionQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<TransactionResponse>> {
+        self.runtime.block_on(self.inner.get_transactions(query))
+    }
+
+    /// Lazily retrieves the transactions on every page that matches the query
+    ///
+    /// ```no_run
+    ///     use tnb_rs::{blocking::RegularNode, models::PaginationOptions};
+    ///
+    ///     let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///
+    ///     for tx in node.transactions_iter(None, PaginationOptions::default()).take(100) {
+    ///         println!("transaction: {:?}", tx.unwrap());
+    ///     }
+    /// ```
+    pub fn transactions_iter(
+        &self,
+        query: Option<&TransactionQueryBuilder<'_>>,
+        options: PaginationOptions,
+    ) -> PaginatedIter<'_, TransactionResponse> {
+        PaginatedIter {
+            stream: self.inner.transactions_stream(query, options),
+            runtime: &self.runtime,
+        }
+    }
+
+    /// Get the blocks stored by this node
+    pub fn get_blocks(
+        &self,
+        query: Option<&BlockQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<BlockResponse>> {
+        self.runtime.block_on(self.inner.get_blocks(query))
+    }
+
+    /// Get the confirmations the node received from validators for its blocks
+    pub fn get_confirmation_blocks(
+        &self,
+        query: Option<&ConfirmationBlockQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<ConfirmationBlockResponse>> {
+        self.runtime
+            .block_on(self.inner.get_confirmation_blocks(query))
+    }
+
+    /// Get the blocks that validators rejected
+    pub fn get_invalid_blocks(
+        &self,
+        query: Option<&InvalidBlockQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<InvalidBlockResponse>> {
+        self.runtime.block_on(self.inner.get_invalid_blocks(query))
+    }
+
+    /// Get the accounts this node knows about
+    pub fn get_accounts(
+        &self,
+        query: Option<&AccountQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<AccountResponse>> {
+        self.runtime.block_on(self.inner.get_accounts(query))
+    }
+
+    /// Changes the trust this node gives to an account
+    pub fn update_account_trust(
+        &self,
+        node_signer: &Account,
+        account_number: &str,
+        trust: i32,
+    ) -> Result<AccountResponse> {
+        self.runtime.block_on(
+            self.inner
+                .update_account_trust(node_signer, account_number, trust),
+        )
+    }
+
+    /// Changes the trust this node gives to another bank
+    pub fn update_bank_trust(
+        &self,
+        node_signer: &Account,
+        node_identifier: &str,
+        trust: i32,
+    ) -> Result<BankResponse> {
+        self.runtime.block_on(
+            self.inner
+                .update_bank_trust(node_signer, node_identifier, trust),
+        )
+    }
+
+    /// Changes the trust this node gives to a validator
+    pub fn update_validator_trust(
+        &self,
+        node_signer: &Account,
+        node_identifier: &str,
+        trust: i32,
+    ) -> Result<ValidatorResponse> {
+        self.runtime.block_on(self.inner.update_validator_trust(
+            node_signer,
+            node_identifier,
+            trust,
+        ))
+    }
+
+    /// Applies every entry of a trust table, in order, and returns the result of each entry
+    pub fn apply_trust_table(&self, node_signer: &Account, table: &TrustTable) -> Vec<TrustUpdate> {
+        self.runtime
+            .block_on(self.inner.apply_trust_table(node_signer, table))
+    }
+
+    /// Get the banks this node knows about
+    pub fn get_banks(
+        &self,
+        query: Option<&BankQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<BankResponse>> {
+        self.runtime.block_on(self.inner.get_banks(query))
+    }
+
+    /// Get the validators this node knows about
+    pub fn get_validators(
+        &self,
+        query: Option<&ValidatorQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<ValidatorResponse>> {
+        self.runtime.block_on(self.inner.get_validators(query))
+    }
+
+    /// Tells another bank that this bank switched to a new primary validator
+    pub fn send_primary_validator_updated(
+        &self,
+        node_signer: &Account,
+        target: &str,
+    ) -> Result<()> {
+        self.runtime.block_on(
+            self.inner
+                .send_primary_validator_updated(node_signer, target),
+        )
+    }
+
+    /// Asks a confirmation validator to become the primary validator of this bank
+    pub fn send_upgrade_request(&self, node_signer: &Account, validator: &str) -> Result<()> {
+        self.runtime
+            .block_on(self.inner.send_upgrade_request(node_signer, validator))
+    }
+
+    /// Retrieve this banks primary validator
+    pub fn get_pv(&self) -> Result<PrimaryValidator> {
+        let pv = self.runtime.block_on(self.inner.get_pv())?;
+
+        Ok(Node {
+            inner: pv,
+            runtime: self.runtime.clone(),
+        })
+    }
+
+    /// add blocks to this banks node then broadcast it to the network
+    pub fn add_blocks(&self, block_data: &BlockType, account: &Account) -> Result<BlockResponse> {
+        self.runtime
+            .block_on(self.inner.add_blocks(block_data, account))
+    }
+
+    /// Broadcast a block that has already been signed
+    pub fn submit_signed_block(&self, block: &BlockMessage) -> Result<BlockResponse> {
+        self.runtime.block_on(self.inner.submit_signed_block(block))
+    }
+
+    /// Finds the accounts of a HD Wallet that have been used on the network
+    ///
+    /// See [`HDWallet::discover_accounts`]
+    pub fn discover_accounts<V: ValidatorTrait + Sync>(
+        &self,
+        hd: &HDWallet,
+        validator: &Node<V>,
+        gap_limit: u32,
+    ) -> Result<Vec<DiscoveredAccount>> {
+        self.runtime
+            .block_on(hd.discover_accounts(&validator.inner, &self.inner, gap_limit))
+    }
+}
+
+impl PrimaryValidator {
+    /// Create a new primary validator instance
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Node::from_async(nodes::PrimaryValidator::new(url)?))
+    }
+
+    /// Create a new primary validator instance that sends requests with the config's settings
+    pub fn with_config(url: &str, config: &ClientConfig) -> Result<Self> {
+        Ok(Node::from_async(nodes::PrimaryValidator::with_config(
+            url, config,
+        )?))
+    }
+
+    /// Create a new primary validator instance that sends requests through the transport
+    pub fn with_transport(url: &str, transport: Arc<dyn Transport>) -> Result<Self> {
+        Ok(Node::from_async(nodes::PrimaryValidator::with_transport(
+            url, transport,
+        )?))
+    }
+}
+
+impl ConfirmationValidator {
+    /// Create a new confirmation validator instance
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Node::from_async(nodes::ConfirmationValidator::new(url)?))
+    }
+
+    /// Create a new confirmation validator instance that sends requests with the config's settings
+    pub fn with_config(url: &str, config: &ClientConfig) -> Result<Self> {
+        Ok(Node::from_async(nodes::ConfirmationValidator::with_config(
+            url, config,
+        )?))
+    }
+
+    /// Create a new confirmation validator instance that sends requests through the transport
+    pub fn with_transport(url: &str, transport: Arc<dyn Transport>) -> Result<Self> {
+        Ok(Node::from_async(
+            nodes::ConfirmationValidator::with_transport(url, transport)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+use crate::testing::MockNetwork;
+
+#[test]
+fn iterate_over_transactions() {
+    let network = MockNetwork::start();
+    for _ in 0..12 {
+        network.fund_account(Account::new().account_number(), 10);
+    }
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+    let options = PaginationOptions {
+        page_size: Some(5),
+        max_items: None,
+    };
+
+    let txs: Vec<_> = node
+        .transactions_iter(None, options)
+        .collect::<Result<_>>()
+        .unwrap();
+
+    assert_eq!(txs.len(), 12);
+}
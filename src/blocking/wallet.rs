@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use tokio::runtime::Runtime;
+
+use crate::{
+    account::Account,
+    blocking::new_runtime,
+    error::Result,
+    models::Transaction,
+    node_pool::NodePool,
+    responses::BlockResponse,
+    wallet::{self, SendOutcome},
+};
+
+/// Blocking version of [`crate::Wallet`]
+///
+/// # Example
+/// ```no_run
+///     use tnb_rs::{blocking::Wallet, Account, models::Transaction};
+///
+///     let sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
+///     let acc = Account::from_signing_key(sk).unwrap();
+///
+///     let mut wallet = Wallet::new(&acc, "https://bank.keysign.app").unwrap();
+///     wallet.init().unwrap();
+///
+///     let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+///     let tx = Transaction::new(recipient, 1000);
+///
+///     let block = wallet.send_transaction(&tx).unwrap();
+///
+/// ```
+pub struct Wallet<'a> {
+    inner: wallet::Wallet<'a>,
+    runtime: Runtime,
+}
+
+impl<'a> Wallet<'a> {
+    /// Create a new Wallet
+    pub fn new(account: &'a Account, node_url: &str) -> Result<Self> {
+        Ok(Wallet {
+            inner: wallet::Wallet::new(account, node_url)?,
+            runtime: new_runtime(),
+        })
+    }
+
+    /// Create a new Wallet that fails over between the nodes in the pool
+    pub fn with_pool(account: &'a Account, pool: NodePool) -> Result<Self> {
+        Ok(Wallet {
+            inner: wallet::Wallet::with_pool(account, pool)?,
+            runtime: new_runtime(),
+        })
+    }
+
+    /// Returns the wrapped async wallet
+    pub fn into_inner(self) -> wallet::Wallet<'a> {
+        self.inner
+    }
+
+    /// Retrieve/Update the node's config files so you can send transactions
+    pub fn init(&mut self) -> Result<()> {
+        self.runtime.block_on(self.inner.init())
+    }
+
+    /// Send a single transaction
+    pub fn send_transaction(&self, tx: &Transaction) -> Result<BlockResponse> {
+        self.runtime.block_on(self.inner.send_transaction(tx))
+    }
+
+    /// Send multiple transactions
+    pub fn send_transactions(&self, txs: &[Transaction]) -> Result<BlockResponse> {
+        self.runtime.block_on(self.inner.send_transactions(txs))
+    }
+
+    /// Send multiple transactions and wait until the block is confirmed or rejected
+    pub fn send_and_confirm(&self, txs: &[Transaction], timeout: Duration) -> Result<SendOutcome> {
+        self.runtime
+            .block_on(self.inner.send_and_confirm(txs, timeout))
+    }
+
+    /// Get the number of coins held in an account
+    pub fn get_balance(&self) -> Result<Option<u64>> {
+        self.runtime.block_on(self.inner.get_balance())
+    }
+
+    /// Switch to a different node to process transaction
+    pub fn switch_node(&mut self, node_url: &str) -> Result<()> {
+        self.runtime.block_on(self.inner.switch_node(node_url))
+    }
+}
+
+#[cfg(test)]
+use crate::testing::MockNetwork;
+
+#[test]
+fn send_transaction_without_a_runtime() {
+    let network = MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 1_000);
+
+    let mut wallet = Wallet::new(&acc, network.bank_url()).unwrap();
+    wallet.init().unwrap();
+
+    let recipient = Account::new();
+    wallet
+        .send_transaction(&Transaction::new(recipient.account_number(), 100))
+        .unwrap();
+
+    assert_eq!(network.balance(recipient.account_number()), Some(100));
+}
@@ -1,177 +1,602 @@
 use crate::{
     account::Account,
-    models::{BlockType, NodeType, Transaction},
+    error::{Error, Result},
+    models::{
+        BlockBuilder, BlockMessage, BlockQueryBuilder, ConfirmationBlockQueryBuilder,
+        InvalidBlockQueryBuilder, NodeType, PaginatedQueryTrait, Transaction,
+    },
+    node_pool::{is_node_failure, NodePool},
     nodes::{PrimaryValidator, RegularNode, ServerNodeTrait, ValidatorTrait},
     responses::{
         AccountBalanceLockResponse, AccountBalanceResponse, BlockResponse, ConfigResponse,
+        ConfirmationBlockResponse, InvalidBlockResponse,
     },
 };
 
-use anyhow::{bail, Result as AnyResult};
-use reqwest::Result;
+/// Whether the validators have confirmed or rejected a block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockStatus {
+    /// The block has not been confirmed or rejected yet
+    Pending,
+
+    /// A validator confirmed the block
+    Confirmed(ConfirmationBlockResponse),
+
+    /// A validator rejected the block
+    Invalid(InvalidBlockResponse),
+}
 
+/// Network client that sends blocks through a regular node and its primary validator
+///
+/// A client created with [`Client::with_pool`] routes requests to the healthiest node in the
+/// pool and retries them on the other nodes when a node goes down.
 pub struct Client {
+    /// The regular node that blocks are broadcasted through
     pub node: RegularNode,
+
+    /// The primary validator selected by the regular node
     pub primary_validator: Option<PrimaryValidator>,
+
+    /// The last retrieved config of the regular node
     pub node_config: Option<ConfigResponse>,
+
+    /// The last retrieved config of the primary validator
     pub primary_validator_config: Option<ConfigResponse>,
+
+    /// The nodes to fail over to when the current node goes down
+    pool: Option<NodePool>,
 }
 
 #[allow(dead_code)]
 impl Client {
     /// Create a new Client
-    pub fn new<'a>(node_url: &'a str) -> Client {
-        let node = RegularNode::new(node_url);
-        Client {
-            node: node,
+    pub fn new(node_url: &str) -> Result<Client> {
+        let node = RegularNode::new(node_url)?;
+        Ok(Client {
+            node,
             primary_validator: None,
             node_config: None,
             primary_validator_config: None,
+            pool: None,
+        })
+    }
+
+    /// Create a new Client that fails over between the nodes in the pool
+    ///
+    /// The healthiest node is selected every time the config is updated
+    pub fn with_pool(pool: NodePool) -> Result<Client> {
+        let healthiest = pool.healthiest().get_base();
+        let node = RegularNode::with_transport(&healthiest.url, healthiest.transport().clone())?;
+
+        Ok(Client {
+            node,
+            primary_validator: None,
+            node_config: None,
+            primary_validator_config: None,
+            pool: Some(pool),
+        })
+    }
+
+    /// The pool of nodes the client fails over between
+    pub fn pool(&self) -> Option<&NodePool> {
+        self.pool.as_ref()
+    }
+
+    /// Switches to the healthiest node in the pool and resolves its primary validator again
+    async fn select_healthiest_node(&mut self) -> Result<()> {
+        if let Some(pool) = &self.pool {
+            pool.probe().await?;
+
+            let healthiest = pool.healthiest().get_base();
+            if healthiest.url != self.node.get_base().url {
+                self.node =
+                    RegularNode::with_transport(&healthiest.url, healthiest.transport().clone())?;
+
+                // the new node may have selected a different primary validator
+                self.invalidate_primary_validator();
+            }
         }
+
+        Ok(())
     }
 
     /// Retrieve the latest config details of your node
-    fn update_node_config(&mut self) {
-        self.node_config = Some(self.node.get_config().unwrap());
+    async fn update_node_config(&mut self) -> Result<()> {
+        self.node_config = Some(self.node.get_config().await?);
+        Ok(())
     }
 
     /// Retrieve the latest config details of your node's selected primary validator
-    fn update_primary_validator_config(&mut self) {
+    async fn update_primary_validator_config(&mut self) -> Result<()> {
         if self.primary_validator.is_none() {
-            self.primary_validator = Some(self.node.get_pv().unwrap());
+            self.primary_validator = Some(self.node.get_pv().await?);
+        }
+
+        if let Some(pv) = self.primary_validator.as_ref() {
+            self.primary_validator_config = Some(pv.get_config().await?);
         }
-        let pv = self.primary_validator.as_ref().unwrap();
-        self.primary_validator_config = Some(pv.get_config().unwrap());
+        Ok(())
     }
 
     /// Rerieve the latest config details for your node and it's selected primary validator
-    pub fn update_config(&mut self) {
-        self.update_primary_validator_config();
-        self.update_node_config();
+    ///
+    /// The cached primary validator is replaced when the node reports that it switched to a new
+    /// primary validator.
+    pub async fn update_config(&mut self) -> Result<()> {
+        self.select_healthiest_node().await?;
+        self.update_node_config().await?;
+
+        if self.primary_validator_changed() {
+            self.invalidate_primary_validator();
+        }
+
+        self.update_primary_validator_config().await
     }
 
-    /// Broadcasts block from your account to the chain
-    fn broadcast_block<'a>(
-        &'a self,
-        block_data: BlockType,
-        sender: &'a Account,
-    ) -> Result<BlockResponse> {
-        self.node.add_blocks(&block_data, sender)
+    /// Forgets the cached primary validator, so it is resolved through the node again on the next
+    /// [`update_config`](Self::update_config)
+    pub fn invalidate_primary_validator(&mut self) {
+        self.primary_validator = None;
+        self.primary_validator_config = None;
+    }
+
+    /// Whether the primary validator in the node's config is not the cached primary validator
+    fn primary_validator_changed(&self) -> bool {
+        let reported = self
+            .node_config
+            .as_ref()
+            .and_then(|config| config.primary_validator.as_ref());
+
+        match (reported, &self.primary_validator_config) {
+            (Some(reported), Some(cached)) => reported.node_identifier != cached.node_identifier,
+            _ => false,
+        }
     }
 
     /// Retrieves the fees for the regular node and primary validator
-    fn get_network_fees<'a>(&self) -> (Transaction, Transaction) {
-        if self.primary_validator.is_some() {
-            let pv_config = self.primary_validator_config.as_ref().unwrap();
-
-            let pv_fee = Transaction {
-                recipient: &pv_config.account_number,
-                amount: pv_config.default_transaction_fee,
-                fee: Some(NodeType::PRIMARY_VALIDATOR),
-                memo: None,
-            };
-
-            let node_config = self.node_config.as_ref().unwrap();
-            let node_fee = Transaction {
-                recipient: &node_config.account_number,
-                amount: node_config.default_transaction_fee,
-                fee: Some(NodeType::BANK),
-                memo: None,
-            };
-
-            (pv_fee, node_fee)
-        } else {
-            panic!("Failed to retrive Primary Validator. Try running '.init()' on the client struct before calling other methods")
+    fn get_network_fees(&self) -> Result<(Transaction, Transaction)> {
+        let pv_config = self
+            .primary_validator_config
+            .as_ref()
+            .ok_or(Error::MissingPrimaryValidatorConfig)?;
+
+        let pv_fee = Transaction {
+            recipient: pv_config.account_number.clone(),
+            amount: pv_config.default_transaction_fee,
+            fee: Some(NodeType::PRIMARY_VALIDATOR),
+            memo: None,
+        };
+
+        let node_config = self.node_config.as_ref().ok_or(Error::MissingNodeConfig)?;
+
+        Ok((pv_fee, bank_fee(node_config)))
+    }
+
+    /// Retrieves the primary validator selected by your node
+    fn get_primary_validator(&self) -> Result<&PrimaryValidator> {
+        self.primary_validator
+            .as_ref()
+            .ok_or(Error::MissingPrimaryValidatorConfig)
+    }
+
+    /// Resolves the primary validator through the pool when the current one fails
+    async fn fallback_primary_validator(&self, e: Error) -> Result<PrimaryValidator> {
+        match &self.pool {
+            Some(pool) if is_node_failure(&e) => pool.request(|node| node.get_pv()).await,
+            _ => Err(e),
         }
     }
 
     /// Retrieves the unique id for an account's next transaction
-    fn get_account_balance_lock<'a>(&self, account: &'a Account) -> AnyResult<Option<String>> {
-        if self.primary_validator.is_some() {
-            let pv = self.primary_validator.as_ref().unwrap();
-            let AccountBalanceLockResponse { balance_lock } = pv
-                .get_account_balance_lock(account.account_number())
-                .unwrap();
-
-            Ok(balance_lock)
-        } else {
-            bail!("Failed to retrive Primary Validator. Try running '.init()' on the client struct before calling other methods")
-        }
+    pub(crate) async fn get_account_balance_lock(
+        &self,
+        account: &Account,
+    ) -> Result<Option<String>> {
+        let account_number = account.account_number();
+
+        let AccountBalanceLockResponse { balance_lock } = match self
+            .get_primary_validator()?
+            .get_account_balance_lock(account_number)
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                self.fallback_primary_validator(e)
+                    .await?
+                    .get_account_balance_lock(account_number)
+                    .await?
+            }
+        };
+
+        Ok(balance_lock)
     }
 
     /// Retrieves the number of coins held by an account
-    pub fn get_account_balance<'a>(&'a self, account_number: &'a str) -> AnyResult<Option<u64>> {
-        let pv = self.primary_validator.as_ref().unwrap();
-        let pv_response = pv.get_account_balance(account_number);
-
-        if pv_response.is_ok() {
-            let response = pv_response.unwrap();
-            let AccountBalanceResponse { balance } = response;
-            Ok(balance)
-        } else {
-            bail!("Failed to retrieve the balance key for {}", account_number);
-        }
+    pub async fn get_account_balance(&self, account_number: &str) -> Result<Option<u64>> {
+        let AccountBalanceResponse { balance } = match self
+            .get_primary_validator()?
+            .get_account_balance(account_number)
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                self.fallback_primary_validator(e)
+                    .await?
+                    .get_account_balance(account_number)
+                    .await?
+            }
+        };
+
+        Ok(balance)
     }
 
     /// Send multiple transactions
-    pub fn send_transactions<'a>(
+    pub async fn send_transactions(
         &self,
-        sender: &'a Account,
-        txs: &'a Vec<Transaction>,
+        sender: &Account,
+        txs: &[Transaction],
     ) -> Result<BlockResponse> {
-        let (pv_fee, node_fee) = self.get_network_fees();
+        let (pv_fee, node_fee) = self.get_network_fees()?;
 
-        let balance_lock = self.get_account_balance_lock(sender).unwrap();
+        let balance_lock = self
+            .get_account_balance_lock(sender)
+            .await?
+            .ok_or_else(|| Error::AccountNeverFunded(sender.account_number().to_string()))?;
 
-        if balance_lock.is_none() {
-            panic!("This Account has never received any coins. Try sending coins to it before making a transaction");
-        }
+        let pool = match &self.pool {
+            Some(pool) => pool,
+            None => {
+                let block = build_block(sender, &balance_lock, &pv_fee, &node_fee, txs)?;
+                return self.node.submit_signed_block(&block).await;
+            }
+        };
+
+        // each node is paid its own fee, so the block is signed again for every node that is tried
+        let (balance_lock, pv_fee) = (&balance_lock, &pv_fee);
+        pool.request_with_config(|node, config| async move {
+            let block = build_block(sender, balance_lock, pv_fee, &bank_fee(&config), txs)?;
+            node.submit_signed_block(&block).await
+        })
+        .await
+    }
+
+    /// Send a single transaction
+    pub async fn send_transaction(
+        &self,
+        sender: &Account,
+        tx: &Transaction,
+    ) -> Result<BlockResponse> {
+        let txs = vec![tx.clone()];
+        self.send_transactions(sender, &txs).await
+    }
 
-        let balance_lock = balance_lock.unwrap();
+    /// Checks whether a block has been confirmed or rejected by the validators
+    ///
+    /// The block can be given by its id or by the balance key it was sent with. Returns
+    /// [`BlockNotFound`](Error::BlockNotFound) if the node has no block with the balance key.
+    /// A client with a node pool asks the healthiest node and fails over to the other nodes.
+    ///
+    /// # Example
+    /// ```no_run
+    ///     use tnb_rs::{Account, BlockStatus, Client, models::Transaction};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let mut client = Client::new("https://bank.keysign.app").unwrap();
+    ///         client.update_config().await.unwrap();
+    ///
+    ///         let acc = Account::new();
+    ///         let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+    ///
+    ///         let block = client.send_transaction(&acc, &Transaction::new(recipient, 1000)).await.unwrap();
+    ///
+    ///         match client.block_status(&block.id).await.unwrap() {
+    ///             BlockStatus::Pending => println!("waiting for the validators"),
+    ///             BlockStatus::Confirmed(confirmation) => println!("confirmed: {:?}", confirmation),
+    ///             BlockStatus::Invalid(rejection) => println!("rejected: {:?}", rejection),
+    ///         }
+    ///     }
+    /// ```
+    pub async fn block_status(&self, block_id_or_balance_key: &str) -> Result<BlockStatus> {
+        match &self.pool {
+            Some(pool) => {
+                pool.request(|node| node_block_status(node, block_id_or_balance_key))
+                    .await
+            }
+            None => node_block_status(&self.node, block_id_or_balance_key).await,
+        }
+    }
+}
 
-        let mut merged_txs = Vec::new();
+/// Looks up the status of a block on a single node
+async fn node_block_status(
+    node: &RegularNode,
+    block_id_or_balance_key: &str,
+) -> Result<BlockStatus> {
+    let block_id = if is_balance_key(block_id_or_balance_key) {
+        let mut block_query = BlockQueryBuilder::new();
+        block_query.balance_key(block_id_or_balance_key).limit(1);
 
-        merged_txs.extend(txs);
-        merged_txs.push(&node_fee);
-        merged_txs.push(&pv_fee);
+        node.get_blocks(Some(&block_query))
+            .await?
+            .results
+            .pop()
+            .ok_or_else(|| Error::BlockNotFound(block_id_or_balance_key.to_string()))?
+            .id
+    } else {
+        block_id_or_balance_key.to_string()
+    };
 
-        let transfer_block = BlockType::coin_transfer(balance_lock, merged_txs);
+    let mut invalid_query = InvalidBlockQueryBuilder::new();
+    invalid_query.block(&block_id).limit(1);
 
-        self.broadcast_block(transfer_block, sender)
+    let invalid = node.get_invalid_blocks(Some(&invalid_query)).await?;
+    if let Some(invalid_block) = invalid.results.into_iter().next() {
+        return Ok(BlockStatus::Invalid(invalid_block));
     }
 
-    /// Send a single transaction
-    pub fn send_transaction<'a>(
-        &self,
-        sender: &'a Account,
-        tx: &'a Transaction,
-    ) -> Result<BlockResponse> {
-        let txs = vec![(*tx).clone()];
-        self.send_transactions(sender, &txs)
+    let mut confirmation_query = ConfirmationBlockQueryBuilder::new();
+    confirmation_query.block(&block_id).limit(1);
+
+    let confirmations = node
+        .get_confirmation_blocks(Some(&confirmation_query))
+        .await?;
+
+    Ok(match confirmations.results.into_iter().next() {
+        Some(confirmation) => BlockStatus::Confirmed(confirmation),
+        None => BlockStatus::Pending,
+    })
+}
+
+/// Balance keys are 64 character hex strings, while block ids are uuids
+fn is_balance_key(key: &str) -> bool {
+    key.len() == 64 && key.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// The fee paid to a regular node for processing a block
+fn bank_fee(config: &ConfigResponse) -> Transaction {
+    Transaction {
+        recipient: config.account_number.clone(),
+        amount: config.default_transaction_fee,
+        fee: Some(NodeType::BANK),
+        memo: None,
     }
 }
 
-#[test]
-fn get_network_fees() {
-    let url = "https://bank.keysign.app";
-    let mut client = Client::new(url);
-    client.update_config();
+fn build_block(
+    sender: &Account,
+    balance_lock: &str,
+    pv_fee: &Transaction,
+    node_fee: &Transaction,
+    txs: &[Transaction],
+) -> Result<BlockMessage> {
+    BlockBuilder::new(sender)
+        .balance_lock(balance_lock)
+        .bank_fee(&node_fee.recipient, node_fee.amount)
+        .primary_validator_fee(&pv_fee.recipient, pv_fee.amount)
+        .transactions(txs)
+        .build()
+}
+
+#[cfg(test)]
+use crate::testing::MockNetwork;
 
-    let (pv_fee, node_fee) = client.get_network_fees();
+#[tokio::test]
+async fn get_network_fees() {
+    let network = MockNetwork::start();
+    let url = network.bank_url();
+    let mut client = Client::new(url).unwrap();
+    client.update_config().await.unwrap();
 
-    let node = RegularNode::new(url);
-    let pv = node.get_pv().unwrap();
+    let (pv_fee, node_fee) = client.get_network_fees().unwrap();
 
-    let pv_config = pv.get_config().unwrap();
+    let node = RegularNode::new(url).unwrap();
+    let pv = node.get_pv().await.unwrap();
+
+    let pv_config = pv.get_config().await.unwrap();
 
     assert_eq!(pv_fee.amount, pv_config.default_transaction_fee);
     assert_eq!(pv_fee.fee, Some(NodeType::PRIMARY_VALIDATOR));
     assert_eq!(pv_fee.memo, None);
     assert_eq!(pv_fee.recipient, pv_config.account_number);
 
-    let node_config = node.get_config().unwrap();
+    let node_config = node.get_config().await.unwrap();
     assert_eq!(node_fee.amount, node_config.default_transaction_fee);
     assert_eq!(node_fee.fee, Some(NodeType::BANK));
     assert_eq!(node_fee.memo, None);
     assert_eq!(node_fee.recipient, node_config.account_number);
 }
+
+#[test]
+fn get_network_fees_before_init() {
+    let client = Client::new("https://bank.keysign.app").unwrap();
+
+    assert!(matches!(
+        client.get_network_fees(),
+        Err(Error::MissingPrimaryValidatorConfig)
+    ));
+}
+
+#[tokio::test]
+async fn send_transactions_from_unfunded_account() {
+    let network = MockNetwork::start();
+    let mut client = Client::new(network.bank_url()).unwrap();
+    client.update_config().await.unwrap();
+
+    let sender = Account::new();
+    let tx = Transaction::new(Account::new().account_number(), 10);
+
+    assert!(matches!(
+        client.send_transaction(&sender, &tx).await,
+        Err(Error::AccountNeverFunded(acc)) if acc == sender.account_number()
+    ));
+}
+
+#[tokio::test]
+async fn send_transactions_through_node_pool() {
+    let network = MockNetwork::start();
+    let down = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let pool = NodePool::new(&[&format!("http://{}", down), network.bank_url()]).unwrap();
+    let mut client = Client::with_pool(pool).unwrap();
+    client.update_config().await.unwrap();
+
+    assert_eq!(client.node.get_base().url, network.bank_url());
+
+    let sender = Account::new();
+    network.fund_account(sender.account_number(), 100);
+
+    let recipient = Account::new();
+    let tx = Transaction::new(recipient.account_number(), 10);
+    client.send_transaction(&sender, &tx).await.unwrap();
+
+    assert_eq!(network.balance(recipient.account_number()), Some(10));
+    assert_eq!(
+        client
+            .get_account_balance(recipient.account_number())
+            .await
+            .unwrap(),
+        Some(10)
+    );
+
+    let health = client.pool().unwrap().health();
+    assert!(!health[0].is_healthy());
+    assert_eq!(health[1].errors, 0);
+}
+
+#[tokio::test]
+async fn block_status_through_node_pool() {
+    let network = MockNetwork::start();
+    let down = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let down = format!("http://{}", down);
+
+    let pool = NodePool::new(&[&down, network.bank_url()]).unwrap();
+    let client = Client::with_pool(pool).unwrap();
+
+    // the pool has not been probed, so the client's node is the one that is down
+    assert_eq!(client.node.get_base().url, down);
+
+    let block = network.fund_account(Account::new().account_number(), 10);
+    assert_eq!(
+        client.block_status(&block.id).await.unwrap(),
+        BlockStatus::Pending
+    );
+
+    network.invalidate_block(&block.id).unwrap();
+    assert!(matches!(
+        client.block_status(&block.balance_key).await.unwrap(),
+        BlockStatus::Invalid(invalid) if invalid.block == block.id
+    ));
+
+    assert!(!client.pool().unwrap().health()[0].is_healthy());
+}
+
+#[tokio::test]
+async fn block_status() {
+    let network = MockNetwork::start();
+    let mut client = Client::new(network.bank_url()).unwrap();
+    client.update_config().await.unwrap();
+
+    let sender = Account::new();
+    network.fund_account(sender.account_number(), 100);
+
+    let tx = Transaction::new(Account::new().account_number(), 10);
+    let block = client.send_transaction(&sender, &tx).await.unwrap();
+
+    assert_eq!(
+        client.block_status(&block.id).await.unwrap(),
+        BlockStatus::Pending
+    );
+
+    let confirmation = network.confirm_block(&block.id).unwrap();
+
+    // the block can also be found by the balance key it was sent with
+    match client.block_status(&block.balance_key).await.unwrap() {
+        BlockStatus::Confirmed(confirmed) => {
+            assert_eq!(confirmed.id, confirmation.id);
+            assert_eq!(confirmed.block, block.id);
+        }
+        status => panic!("expected a confirmed block but found {:?}", status),
+    }
+}
+
+#[tokio::test]
+async fn invalid_block_status() {
+    let network = MockNetwork::start();
+    let client = Client::new(network.bank_url()).unwrap();
+
+    let block = network.fund_account(Account::new().account_number(), 10);
+    network.invalidate_block(&block.id).unwrap();
+
+    assert!(matches!(
+        client.block_status(&block.id).await.unwrap(),
+        BlockStatus::Invalid(invalid) if invalid.block == block.id
+    ));
+
+    let unknown_balance_key = Account::new().account_number().to_string();
+    assert!(matches!(
+        client.block_status(&unknown_balance_key).await,
+        Err(Error::BlockNotFound(key)) if key == unknown_balance_key
+    ));
+}
+
+#[tokio::test]
+async fn follow_primary_validator_switch() {
+    let network = MockNetwork::start();
+    let other_network = MockNetwork::start();
+
+    let mut client = Client::new(network.bank_url()).unwrap();
+    client.update_config().await.unwrap();
+
+    network.add_bank(other_network.bank_entry());
+    network.add_validator(other_network.primary_validator_entry());
+
+    // the other bank tells the client's bank that it switched to its own primary validator
+    let other_bank = RegularNode::new(other_network.bank_url()).unwrap();
+    other_bank
+        .send_primary_validator_updated(other_network.bank_signer(), network.bank_url())
+        .await
+        .unwrap();
+
+    let pv_identifier = other_network.primary_validator_entry().node_identifier;
+    assert_ne!(
+        client
+            .primary_validator_config
+            .as_ref()
+            .unwrap()
+            .node_identifier,
+        pv_identifier
+    );
+
+    client.update_config().await.unwrap();
+
+    assert_eq!(
+        client
+            .primary_validator_config
+            .as_ref()
+            .unwrap()
+            .node_identifier,
+        pv_identifier
+    );
+    assert_eq!(
+        client.primary_validator.as_ref().unwrap().get_base().url,
+        other_network.primary_validator_url()
+    );
+
+    // balances are now read from the new primary validator
+    let acc = Account::new();
+    other_network.fund_account(acc.account_number(), 10);
+    assert_eq!(
+        client
+            .get_account_balance(acc.account_number())
+            .await
+            .unwrap(),
+        Some(10)
+    );
+}
@@ -0,0 +1,205 @@
+use crate::{
+    account::Account,
+    error::{Error, Result},
+    hd_wallet::{HDWallet, MAX_CHILD_INDEX},
+    models::{PaginatedQueryTrait, TransactionQueryBuilder},
+    nodes::{RegularNode, ValidatorTrait},
+};
+
+/// The number of consecutive unused addresses the bip44 standard scans before it stops
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// An account derived from a HD Wallet that has been used on the network
+#[derive(Debug)]
+pub struct DiscoveredAccount {
+    /// The derived account
+    pub account: Account,
+
+    /// Index of the account in the bip44 path
+    pub account_index: u32,
+
+    /// Index of the address in the bip44 path
+    pub address_index: u32,
+
+    /// The number of coins held by the account
+    pub balance: u64,
+}
+
+impl HDWallet {
+    /// Finds the accounts of the HD Wallet that have been used on the network
+    ///
+    /// Follows the bip44 account discovery process. The addresses of each account
+    /// (`m/44'/2002'/{account_index}'/0'/{address_index}'`) are derived in order until `gap_limit`
+    /// consecutive addresses are unused, and the scan moves on to the next account until it finds
+    /// an account without any used addresses.
+    ///
+    /// An address is used if the validator has a balance for it or the node has transactions sent
+    /// to or from it. Returns [`InvalidGapLimit`](Error::InvalidGapLimit) if `gap_limit` is 0.
+    ///
+    /// # Example
+    /// ```no_run
+    ///     use tnb_rs::{HDWallet, DEFAULT_GAP_LIMIT, nodes::RegularNode};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let mnemonic = "visa nephew like this amazing soldier negative front elevator warfare teach good";
+    ///         let hd = HDWallet::from_mnemonic(mnemonic, None).unwrap();
+    ///
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///         let pv = node.get_pv().await.unwrap();
+    ///
+    ///         let accounts = hd.discover_accounts(&pv, &node, DEFAULT_GAP_LIMIT).await.unwrap();
+    ///
+    ///         for discovered in accounts {
+    ///             println!(
+    ///                 "{}/{}: {} has {} coins",
+    ///                 discovered.account_index,
+    ///                 discovered.address_index,
+    ///                 discovered.account.account_number(),
+    ///                 discovered.balance
+    ///             );
+    ///         }
+    ///     }
+    /// ```
+    pub async fn discover_accounts<V>(
+        &self,
+        validator: &V,
+        node: &RegularNode,
+        gap_limit: u32,
+    ) -> Result<Vec<DiscoveredAccount>>
+    where
+        V: ValidatorTrait + Sync,
+    {
+        if gap_limit == 0 {
+            return Err(Error::InvalidGapLimit);
+        }
+
+        let mut discovered = vec![];
+
+        for account_index in 0..=MAX_CHILD_INDEX {
+            let mut is_account_used = false;
+            let mut unused_addresses = 0;
+
+            for address_index in 0..=MAX_CHILD_INDEX {
+                if unused_addresses >= gap_limit {
+                    break;
+                }
+
+                let account = self.get_account(account_index, address_index)?;
+
+                match account_usage(&account, validator, node).await? {
+                    Some(balance) => {
+                        is_account_used = true;
+                        unused_addresses = 0;
+
+                        discovered.push(DiscoveredAccount {
+                            account,
+                            account_index,
+                            address_index,
+                            balance,
+                        });
+                    }
+                    None => unused_addresses += 1,
+                }
+            }
+
+            if !is_account_used {
+                break;
+            }
+        }
+
+        Ok(discovered)
+    }
+}
+
+/// Returns the balance of the account if it has been used on the network
+async fn account_usage<V>(
+    account: &Account,
+    validator: &V,
+    node: &RegularNode,
+) -> Result<Option<u64>>
+where
+    V: ValidatorTrait + Sync,
+{
+    let balance = validator
+        .get_account_balance(account.account_number())
+        .await?
+        .balance;
+
+    if balance.is_some() {
+        return Ok(balance);
+    }
+
+    let mut query = TransactionQueryBuilder::new();
+    query.account_number(account.account_number()).limit(1);
+
+    let txs = node.get_transactions(Some(&query)).await?;
+
+    Ok(if txs.count > 0 { Some(0) } else { None })
+}
+
+#[cfg(test)]
+use crate::{nodes::PrimaryValidator, testing::MockNetwork};
+
+#[tokio::test]
+async fn discover_used_accounts() {
+    let network = MockNetwork::start();
+    let hd = HDWallet::new();
+
+    for (account_index, address_index, amount) in [(0, 0, 10), (0, 2, 20), (0, 6, 30), (1, 0, 40)] {
+        let acc = hd.get_account(account_index, address_index).unwrap();
+        network.fund_account(acc.account_number(), amount);
+    }
+
+    // outside the gap limit of account 0
+    let acc = hd.get_account(0, 12).unwrap();
+    network.fund_account(acc.account_number(), 50);
+
+    // account 2 is unused so account 3 is never scanned
+    let acc = hd.get_account(3, 0).unwrap();
+    network.fund_account(acc.account_number(), 60);
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+
+    let discovered = hd.discover_accounts(&pv, &node, 5).await.unwrap();
+    let found: Vec<_> = discovered
+        .iter()
+        .map(|d| (d.account_index, d.address_index, d.balance))
+        .collect();
+
+    assert_eq!(found, vec![(0, 0, 10), (0, 2, 20), (0, 6, 30), (1, 0, 40)]);
+    assert_eq!(
+        discovered[1].account.account_number(),
+        hd.get_account(0, 2).unwrap().account_number()
+    );
+}
+
+#[tokio::test]
+async fn discover_accounts_of_unused_wallet() {
+    let network = MockNetwork::start();
+    let node = RegularNode::new(network.bank_url()).unwrap();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+
+    let discovered = HDWallet::new()
+        .discover_accounts(&pv, &node, DEFAULT_GAP_LIMIT)
+        .await
+        .unwrap();
+
+    assert!(discovered.is_empty());
+}
+
+#[tokio::test]
+async fn discover_accounts_with_zero_gap_limit() {
+    let network = MockNetwork::start();
+    let hd = HDWallet::new();
+    network.fund_account(hd.get_first_account().unwrap().account_number(), 10);
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+
+    assert!(matches!(
+        hd.discover_accounts(&pv, &node, 0).await,
+        Err(Error::InvalidGapLimit)
+    ));
+}
@@ -0,0 +1,135 @@
+use thiserror::Error as ThisError;
+
+use crate::models::ChainDataKind;
+
+/// A specialized `Result` type for this crate
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The error type returned by every fallible function in this crate
+#[derive(Debug, ThisError)]
+pub enum Error {
+    /// A key, signature or seed was not a valid hex string
+    #[error("Invalid hex string: {0}")]
+    InvalidHex(#[from] hex::FromHexError),
+
+    /// A hex key had the wrong number of characters
+    #[error("Expected a hex key of length {expected} but found {found}")]
+    InvalidKeyLength {
+        /// the number of hex characters the key should have
+        expected: usize,
+        /// the number of hex characters the key has
+        found: usize,
+    },
+
+    /// The mnemonic phrase is not a valid bip39 mnemonic
+    #[error("Invalid Mnemonic: {0}")]
+    InvalidMnemonic(String),
+
+    /// The seed could not be used to create a HD Wallet
+    #[error("Invalid Seed: The seed has to be a valid hex string of length 64")]
+    InvalidSeed,
+
+    /// The bip44 path could not be parsed or derived
+    #[error("Invalid Derivation Path: {0}")]
+    InvalidDerivationPath(String),
+
+    /// An `account_index` or `address_index` is greater than [`MAX_CHILD_INDEX`](crate::MAX_CHILD_INDEX)
+    #[error("{name} ({index}) is greater than the Max child index (2_147_483_647)")]
+    IndexOutOfRange {
+        /// the name of the index
+        name: &'static str,
+        /// the index that was given
+        index: u32,
+    },
+
+    /// A trust value is outside the range the network accepts
+    #[error("Invalid trust {0}: Trust has to be between 0 and 100")]
+    InvalidTrust(i32),
+
+    /// A transaction memo contains characters the network does not accept
+    #[error("Invalid memo {0:?}: Memo can only contain alphanumeric values (Aa - Zz, 0 - 9), space and an underscore (_)")]
+    InvalidMemo(String),
+
+    /// A node url could not be parsed
+    #[error("Invalid Url: {0}")]
+    InvalidUrl(String),
+
+    /// A header set on the [`ClientConfig`](crate::nodes::ClientConfig) has an invalid name or value
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+
+    /// The request to a node could not be completed
+    #[error("Request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// A node responded with an unsuccessful status code
+    #[error("Node responded with status {status}: {body}")]
+    HttpStatus {
+        /// the http status code
+        status: u16,
+        /// the body of the response
+        body: String,
+    },
+
+    /// A response or message could not be decoded
+    #[error("Failed to decode json: {0}")]
+    Decode(#[from] serde_json::Error),
+
+    /// The paginated response has no next or previous link
+    #[error("{0} link is empty")]
+    MissingPageLink(&'static str),
+
+    /// A file could not be read or written
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A [`ReplayTransport`](crate::transport::ReplayTransport) has no recorded response for the request
+    #[error("No recorded response for {0}. Record the fixture again to include the request")]
+    MissingFixture(String),
+
+    /// The node has no block with the balance key
+    #[error("The node has no block with the balance key {0}")]
+    BlockNotFound(String),
+
+    /// A signed message does not have the shape of the request the endpoint expects
+    #[error("Invalid message: The message is not a {0:?} request")]
+    UnexpectedMessage(ChainDataKind),
+
+    /// Account discovery was started with a gap limit of 0, which would stop before scanning any address
+    #[error("Invalid gap limit: The gap limit has to be at least 1")]
+    InvalidGapLimit,
+
+    /// A node pool was created without any node urls
+    #[error("A node pool needs at least one node url")]
+    EmptyNodePool,
+
+    /// The node of a client that selects its node from a pool can't be switched manually
+    #[error("The node can't be switched on a client with a node pool. The healthiest node in the pool is always selected")]
+    PooledNodeSwitch,
+
+    /// The node's config has not been retrieved
+    #[error(
+        "Failed to retrieve the node's config. Try running '.init()' before calling other methods"
+    )]
+    MissingNodeConfig,
+
+    /// The node has no primary validator config or it has not been retrieved
+    #[error("Failed to retrieve the Primary Validator's config. Try running '.init()' before calling other methods")]
+    MissingPrimaryValidatorConfig,
+
+    /// The account has no balance lock because it has never received any coins
+    #[error("The account {0} has never received any coins. Try sending coins to it before making a transaction")]
+    AccountNeverFunded(String),
+
+    /// A keystore could not be parsed or uses an unsupported format
+    #[error("Invalid Keystore: {0}")]
+    InvalidKeystore(String),
+
+    /// A keystore could not be decrypted with the given password
+    #[error("Incorrect password or the keystore has been modified")]
+    IncorrectPassword,
+
+    /// A required field was not set on the [`BlockBuilder`](crate::models::BlockBuilder)
+    #[error("The block is missing the {0}. Set it on the BlockBuilder before building the block")]
+    IncompleteBlock(&'static str),
+}
@@ -1,11 +1,14 @@
-use crate::account::*;
+use crate::{
+    account::*,
+    error::{Error, Result},
+    keystore::{Keystore, KeystoreKind},
+};
 
-use anyhow::{ensure, Result};
 use bip39::{Language, Mnemonic};
 use ed25519_dalek_bip32::{DerivationPath, ExtendedSecretKey};
-use hex;
-use rand;
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use zeroize::{Zeroize, Zeroizing};
 
 /// The max number for the account_index and address_index
 pub const MAX_CHILD_INDEX: u32 = 2_147_483_647;
@@ -31,39 +34,62 @@ pub const MAX_CHILD_INDEX: u32 = 2_147_483_647;
 /// The `account_index` and `address_index` can be changed by the user to get their desired account
 ///
 /// With this format a user can get up to ```4,611,686,014,132,420,600``` accounts from a single mnemonic phrase
-
+///
+/// The mnemonic phrase, password and seed are wiped from memory when the wallet is dropped and they
+/// are redacted from the wallet's `Debug` output. Use [`HDWallet::reveal_secrets`] to print them.
 pub struct HDWallet {
     /// mnemonic phrase
-    mnemonic: String,
-    seed: Vec<u8>,
-    master_key: ExtendedSecretKey,
+    mnemonic: Zeroizing<String>,
+    /// optional bip39 password used with the mnemonic phrase to create the seed
+    password: Option<Zeroizing<String>>,
+    seed: Zeroizing<Vec<u8>>,
 }
 
 impl fmt::Debug for HDWallet {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("HD Wallet")
-            .field("mnemonic", &self.mnemonic())
+            .field("mnemonic", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Debug output of a HD Wallet that includes its mnemonic phrase
+struct RevealedHDWallet<'a>(&'a HDWallet);
+
+impl fmt::Debug for RevealedHDWallet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HD Wallet")
+            .field("mnemonic", &self.0.mnemonic())
+            .field("seed", &self.0.seed_hex().as_str())
             .finish()
     }
 }
 
+impl Default for HDWallet {
+    fn default() -> Self {
+        HDWallet::new()
+    }
+}
+
 #[allow(dead_code)]
 impl HDWallet {
+    /// The mnemonic's entropy is wiped when it is dropped at the end of this function
     fn create(mnemonic: Mnemonic, password: Option<&str>) -> Self {
-        let seed = mnemonic
-            .to_seed(match password {
-                Some(pw) => pw,
-                None => "",
-            })
-            .to_vec();
-        let xpriv = ExtendedSecretKey::from_seed(&seed).unwrap();
+        let seed = Zeroizing::new(mnemonic.to_seed(password.unwrap_or_default()));
 
         HDWallet {
-            mnemonic: mnemonic.to_string(),
-            seed: seed,
-            master_key: xpriv,
+            mnemonic: Zeroizing::new(mnemonic.to_string()),
+            password: password.map(|p| Zeroizing::new(p.to_string())),
+            seed: Zeroizing::new(seed.to_vec()),
         }
     }
+
+    /// Derives the master key from the seed
+    fn master_key(&self) -> Result<WipedSecretKey> {
+        ExtendedSecretKey::from_seed(&self.seed)
+            .map(WipedSecretKey)
+            .map_err(|_| Error::InvalidSeed)
+    }
     /// Creates a new HD Wallet
     ///
     /// ```
@@ -73,12 +99,8 @@ impl HDWallet {
     /// ```
     ///  Ensure that you save your mnemonic phrase somewhere secure so you can use it again later
     ///
-    ///
-
     pub fn new() -> Self {
-        let mut rng = rand::thread_rng();
-        let mnemonic = Mnemonic::generate_in_with(&mut rng, Language::English, 12).unwrap();
-        HDWallet::create(mnemonic, None)
+        HDWallet::new_with_password(None)
     }
 
     /// Creates a HD Wallet from a specified mnemonic phrase.
@@ -107,48 +129,108 @@ impl HDWallet {
     ///
     ///
     pub fn from_mnemonic(mnemonic: &str, password: Option<&str>) -> Result<HDWallet> {
-        let result = Mnemonic::parse_normalized(mnemonic);
-        ensure!(result.is_ok(), "Invalid Mnemonic");
+        let m = Mnemonic::parse_normalized(mnemonic)
+            .map_err(|e| Error::InvalidMnemonic(e.to_string()))?;
 
-        let m = result.unwrap();
         Ok(HDWallet::create(m, password))
     }
 
     /// Creates a new HD Wallet with an optional field for a password
     pub fn new_with_password(password: Option<&str>) -> Self {
         let mut rng = rand::thread_rng();
-        let mnemonic = Mnemonic::generate_in_with(&mut rng, Language::English, 12).unwrap();
-        HDWallet::from_mnemonic(mnemonic.to_string().as_ref(), password).unwrap()
+        let mnemonic = Mnemonic::generate_in_with(&mut rng, Language::English, 12)
+            .expect("12 is a valid mnemonic word count");
+        HDWallet::create(mnemonic, password)
     }
 
     /// Creates a HD Wallet from a seed in hex string format
     ///
     pub fn from_seed(seed: &str) -> Result<Self> {
-        let seed_as_bytes = hex::decode(&seed).unwrap();
-        let result = ExtendedSecretKey::from_seed(&seed_as_bytes);
-
-        ensure!(
-            result.is_ok(),
-            "Invalid Seed: The seed has to be a valid hex string of length 64"
-        );
-
-        Ok(HDWallet {
-            mnemonic: "".to_string(),
-            seed: seed_as_bytes,
-            master_key: result.unwrap(),
-        })
+        let hd = HDWallet {
+            mnemonic: Zeroizing::new(String::new()),
+            password: None,
+            seed: Zeroizing::new(hex::decode(seed)?),
+        };
+
+        hd.master_key()?;
+        Ok(hd)
+    }
+
+    /// Encrypts the mnemonic phrase and its optional password with the `keystore_password` and returns the keystore as a json string
+    ///
+    /// Wallets created with [`HDWallet::from_seed`] store the seed instead
+    ///
+    /// # Example
+    /// ```
+    ///     use tnb_rs::HDWallet;
+    ///
+    ///     let mnemonic = "visa nephew like this amazing soldier negative front elevator warfare teach good";
+    ///     let hd = HDWallet::from_mnemonic(mnemonic, Some("bip39 password")).unwrap();
+    ///
+    ///     let keystore = hd.to_keystore("keystore password").unwrap();
+    ///
+    ///     let restored_hd = HDWallet::from_keystore(&keystore, "keystore password").unwrap();
+    ///
+    ///     assert_eq!(restored_hd.mnemonic(), mnemonic);
+    ///     assert_eq!(restored_hd.seed_hex(), hd.seed_hex());
+    /// ```
+    pub fn to_keystore(&self, keystore_password: &str) -> Result<String> {
+        let secret = if self.mnemonic.is_empty() {
+            WalletSecret::Seed {
+                seed: hex::encode(self.seed.as_slice()),
+            }
+        } else {
+            WalletSecret::Mnemonic {
+                mnemonic: self.mnemonic.to_string(),
+                password: self.password.as_ref().map(|p| p.to_string()),
+            }
+        };
+
+        let secret_json = Zeroizing::new(serde_json::to_vec(&secret)?);
+
+        Keystore::encrypt(
+            KeystoreKind::HdWallet,
+            None,
+            &secret_json,
+            keystore_password,
+        )?
+        .to_json()
+    }
+
+    /// Decrypts a HD Wallet from a keystore created with [`HDWallet::to_keystore`]
+    pub fn from_keystore(keystore_json: &str, keystore_password: &str) -> Result<Self> {
+        let keystore = Keystore::from_json(keystore_json, KeystoreKind::HdWallet)?;
+        let secret_json = keystore.decrypt(keystore_password)?;
+
+        let secret: WalletSecret = serde_json::from_slice(&secret_json)
+            .map_err(|e| Error::InvalidKeystore(format!("Failed to parse the wallet: {}", e)))?;
+
+        match &secret {
+            WalletSecret::Mnemonic { mnemonic, password } => {
+                HDWallet::from_mnemonic(mnemonic, password.as_deref())
+            }
+            WalletSecret::Seed { seed } => HDWallet::from_seed(seed),
+        }
     }
 
     /// Retrieves the account specified by a bip44 path
     fn get_account_from_path(&self, path: &str) -> Result<Account> {
-        let result = self
-            .master_key
-            .derive::<DerivationPath>(&path.parse().unwrap());
-        ensure!(result.is_ok(), "Invalid Derivation Path");
+        let derivation_path = path
+            .parse::<DerivationPath>()
+            .map_err(|e| Error::InvalidDerivationPath(format!("{}: {:?}", path, e)))?;
 
-        let child_xpriv = result.unwrap();
+        // derived one level at a time so every intermediate key is wiped
+        let mut child_xpriv = self.master_key()?;
+        for index in derivation_path.as_ref() {
+            child_xpriv = child_xpriv
+                .0
+                .derive_child(*index)
+                .map(WipedSecretKey)
+                .map_err(|e| Error::InvalidDerivationPath(format!("{}: {:?}", path, e)))?;
+        }
 
-        Account::from_signing_key(&hex::encode(child_xpriv.secret_key))
+        let signing_key = Zeroizing::new(child_xpriv.0.secret_key.to_bytes());
+        Ok(Account::from_signing_key_bytes(&signing_key))
     }
 
     /// Retrieves the account specified by the account_index and address_index from the HD Wallet
@@ -160,29 +242,38 @@ impl HDWallet {
     ///     let mnemonic = "visa nephew like this amazing soldier negative front elevator warfare teach good";
     ///     let hd = HDWallet::from_mnemonic(mnemonic, None).unwrap();
     ///     
-    ///     let acc1 = hd.get_account(0, 0);
-    ///     let acc2 = hd.get_account(23, 1_000);
+    ///     let acc1 = hd.get_account(0, 0).unwrap();
+    ///     let acc2 = hd.get_account(23, 1_000).unwrap();
     ///
-    ///     let last_account = hd.get_account(MAX_CHILD_INDEX, MAX_CHILD_INDEX);
+    ///     let last_account = hd.get_account(MAX_CHILD_INDEX, MAX_CHILD_INDEX).unwrap();
+    ///
+    ///     // indexes greater than the MAX_CHILD_INDEX return an error
+    ///     assert!(hd.get_account(MAX_CHILD_INDEX + 1, 0).is_err());
     ///
     /// ```
     ///
     ///
-    pub fn get_account(&self, account_index: u32, address_index: u32) -> Account {
+    pub fn get_account(&self, account_index: u32, address_index: u32) -> Result<Account> {
         let path = format!("m/44'/2002'/{}'/0'/{}'", account_index, address_index);
 
         if account_index > MAX_CHILD_INDEX {
-            panic!("Account Index is greater than the Max child index (2_147_483_647)")
+            return Err(Error::IndexOutOfRange {
+                name: "Account Index",
+                index: account_index,
+            });
         }
         if address_index > MAX_CHILD_INDEX {
-            panic!("Address Index is greater than the Max child index (2_147_483_647)")
+            return Err(Error::IndexOutOfRange {
+                name: "Address Index",
+                index: address_index,
+            });
         }
 
-        self.get_account_from_path(&path).unwrap()
+        self.get_account_from_path(&path)
     }
 
     /// Retrieves an account specified by the address_index from the column where (account_index == 0)
-    pub fn get_account_from_first_col(&self, address_index: u32) -> Account {
+    pub fn get_account_from_first_col(&self, address_index: u32) -> Result<Account> {
         self.get_account(0, address_index)
     }
 
@@ -195,23 +286,40 @@ impl HDWallet {
     ///     let mnemonic = "visa nephew like this amazing soldier negative front elevator warfare teach good";
     ///     let hd = HDWallet::from_mnemonic(mnemonic, None).unwrap();
     ///
-    ///     let get_first_account = hd.get_first_account();
+    ///     let get_first_account = hd.get_first_account().unwrap();
     ///
     ///     assert_eq!(get_first_account.account_number(),
-    ///                 hd.get_account(0, 0).account_number());
+    ///                 hd.get_account(0, 0).unwrap().account_number());
     ///     assert_eq!(get_first_account.signing_key(),
-    ///                 hd.get_account(0, 0).signing_key());
+    ///                 hd.get_account(0, 0).unwrap().signing_key());
     ///
     ///
     /// ```
     ///
-    pub fn get_first_account(&self) -> Account {
+    pub fn get_first_account(&self) -> Result<Account> {
         self.get_account(0, 0)
     }
 
     /// Returns the seed in hex format
-    pub fn seed_hex(&self) -> String {
-        hex::encode(&self.seed)
+    ///
+    /// The hex string is created on each call and wiped from memory when it is dropped
+    pub fn seed_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(self.seed.as_slice()))
+    }
+
+    /// Returns a value whose `Debug` output includes the mnemonic phrase and seed
+    ///
+    /// # Example
+    /// ```
+    ///     use tnb_rs::HDWallet;
+    ///
+    ///     let hd = HDWallet::new();
+    ///
+    ///     assert!(!format!("{:?}", hd).contains(hd.mnemonic()));
+    ///     assert!(format!("{:?}", hd.reveal_secrets()).contains(hd.mnemonic()));
+    /// ```
+    pub fn reveal_secrets(&self) -> impl fmt::Debug + '_ {
+        RevealedHDWallet(self)
     }
 
     /// Returns a reference to the mnemonic phrase`
@@ -220,6 +328,42 @@ impl HDWallet {
     }
 }
 
+/// An extended secret key whose chain code is wiped when it is dropped
+///
+/// The secret key wipes itself, but `ExtendedSecretKey` leaves its chain code in memory
+struct WipedSecretKey(ExtendedSecretKey);
+
+impl Drop for WipedSecretKey {
+    fn drop(&mut self) {
+        self.0.chain_code.zeroize();
+    }
+}
+
+/// The secret stored in a HD Wallet's keystore
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WalletSecret {
+    Mnemonic {
+        mnemonic: String,
+        password: Option<String>,
+    },
+    Seed {
+        seed: String,
+    },
+}
+
+impl Drop for WalletSecret {
+    fn drop(&mut self) {
+        match self {
+            WalletSecret::Mnemonic { mnemonic, password } => {
+                mnemonic.zeroize();
+                password.zeroize();
+            }
+            WalletSecret::Seed { seed } => seed.zeroize(),
+        }
+    }
+}
+
 #[test]
 fn test_invalid_mnemonic_returns_err() {
     let hd = HDWallet::from_mnemonic(
@@ -227,7 +371,7 @@ fn test_invalid_mnemonic_returns_err() {
         None,
     );
 
-    assert_eq!(hd.is_err(), true);
+    assert!(hd.is_err());
 }
 
 #[test]
@@ -246,7 +390,7 @@ fn test_vectors_for_ed25519() {
         "86fab68dcb57aa196c77c5f264f215a112c22a912c10d123b0d03c3c28ef1037"
     );
     assert_eq!(
-        acc.signing_key(),
+        acc.signing_key().as_str(),
         "1559eb2bbec5790b0c65d8693e4d0875b1747f4970ae8b650486ed7470845635"
     );
     let acc = hd.get_account_from_path("m/0'/2147483647'").unwrap();
@@ -255,7 +399,7 @@ fn test_vectors_for_ed25519() {
         "5ba3b9ac6e90e83effcd25ac4e58a1365a9e35a3d3ae5eb07b9e4d90bcf7506d"
     );
     assert_eq!(
-        acc.signing_key(),
+        acc.signing_key().as_str(),
         "ea4f5bfe8694d8bb74b7b59404632fd5968b774ed545e810de9c32a4fb4192f4"
     );
     let acc = hd
@@ -266,7 +410,52 @@ fn test_vectors_for_ed25519() {
         "47150c75db263559a70d5778bf36abbab30fb061ad69f69ece61a72b0cfa4fc0"
     );
     assert_eq!(
-        acc.signing_key(),
+        acc.signing_key().as_str(),
         "551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d"
     );
 }
+
+#[test]
+fn test_index_out_of_range_returns_err() {
+    let hd = HDWallet::new();
+
+    assert!(matches!(
+        hd.get_account(MAX_CHILD_INDEX + 1, 0),
+        Err(Error::IndexOutOfRange {
+            name: "Account Index",
+            ..
+        })
+    ));
+    assert!(matches!(
+        hd.get_account(0, u32::MAX),
+        Err(Error::IndexOutOfRange {
+            name: "Address Index",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_keystore_from_seed() {
+    let seed =
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542";
+    let hd = HDWallet::from_seed(seed).unwrap();
+
+    let keystore = hd.to_keystore("password").unwrap();
+    let restored_hd = HDWallet::from_keystore(&keystore, "password").unwrap();
+
+    assert_eq!(restored_hd.seed_hex().as_str(), seed);
+    assert_eq!(
+        restored_hd.get_first_account().unwrap(),
+        hd.get_first_account().unwrap()
+    );
+
+    assert!(matches!(
+        HDWallet::from_keystore(&keystore, "wrong password"),
+        Err(Error::IncorrectPassword)
+    ));
+    assert!(matches!(
+        Account::from_keystore(&keystore, "password"),
+        Err(Error::InvalidKeystore(_))
+    ));
+}
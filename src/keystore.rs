@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use sodiumoxide::crypto::{
+    pwhash::argon2id13::{self, MemLimit, OpsLimit, Salt},
+    secretbox::{self, Key, Nonce},
+};
+use zeroize::Zeroizing;
+
+use crate::error::{Error, Result};
+
+/// The current version of the keystore format
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const KDF_NAME: &str = "argon2id13";
+
+/// The argon2id limits written by [`Keystore::encrypt`], which are also the highest limits a
+/// keystore is decrypted with, so a crafted keystore can't make decryption use unbounded memory
+/// and cpu time
+const KDF_OPSLIMIT: OpsLimit = argon2id13::OPSLIMIT_INTERACTIVE;
+const KDF_MEMLIMIT: MemLimit = argon2id13::MEMLIMIT_INTERACTIVE;
+const CIPHER_NAME: &str = "xsalsa20poly1305";
+
+/// The type of secret stored in a keystore
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeystoreKind {
+    /// The signing key of an [`Account`](crate::Account)
+    Account,
+
+    /// The mnemonic phrase or seed of a [`HDWallet`](crate::HDWallet)
+    HdWallet,
+}
+
+/// The parameters used to derive the encryption key from the password
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KdfParams {
+    salt: String,
+    opslimit: usize,
+    memlimit: usize,
+}
+
+/// The encrypted secret and everything needed to decrypt it except the password
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeystoreCrypto {
+    kdf: String,
+    kdf_params: KdfParams,
+    cipher: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// A secret encrypted with a password
+///
+/// The encryption key is derived from the password with argon2id and the secret is encrypted and
+/// authenticated with xsalsa20poly1305
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    version: u32,
+    kind: KeystoreKind,
+
+    /// public account number stored next to an account's signing key so the keystore can be identified without the password
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    account_number: Option<String>,
+
+    crypto: KeystoreCrypto,
+}
+
+impl Keystore {
+    /// Encrypts the secret with a key derived from the password
+    pub fn encrypt(
+        kind: KeystoreKind,
+        account_number: Option<&str>,
+        secret: &[u8],
+        password: &str,
+    ) -> Result<Self> {
+        let salt = argon2id13::gen_salt();
+        let (opslimit, memlimit) = (KDF_OPSLIMIT, KDF_MEMLIMIT);
+
+        let key = derive_key(password, &salt, opslimit, memlimit)?;
+        let nonce = secretbox::gen_nonce();
+        let ciphertext = secretbox::seal(secret, &nonce, &key);
+
+        Ok(Keystore {
+            version: KEYSTORE_VERSION,
+            kind,
+            account_number: account_number.map(|acc| acc.to_string()),
+            crypto: KeystoreCrypto {
+                kdf: KDF_NAME.to_string(),
+                kdf_params: KdfParams {
+                    salt: hex::encode(salt),
+                    opslimit: opslimit.0,
+                    memlimit: memlimit.0,
+                },
+                cipher: CIPHER_NAME.to_string(),
+                nonce: hex::encode(nonce),
+                ciphertext: hex::encode(ciphertext),
+            },
+        })
+    }
+
+    /// Parses a keystore and checks that it holds the expected kind of secret
+    pub fn from_json(json: &str, kind: KeystoreKind) -> Result<Self> {
+        let keystore: Keystore = serde_json::from_str(json)
+            .map_err(|e| Error::InvalidKeystore(format!("Failed to parse keystore: {}", e)))?;
+
+        if keystore.version != KEYSTORE_VERSION {
+            return Err(Error::InvalidKeystore(format!(
+                "Unsupported keystore version {}",
+                keystore.version
+            )));
+        }
+
+        if keystore.kind != kind {
+            return Err(Error::InvalidKeystore(format!(
+                "Expected a keystore for {:?} but found {:?}",
+                kind, keystore.kind
+            )));
+        }
+
+        if keystore.crypto.kdf != KDF_NAME || keystore.crypto.cipher != CIPHER_NAME {
+            return Err(Error::InvalidKeystore(format!(
+                "Unsupported kdf ({}) or cipher ({})",
+                keystore.crypto.kdf, keystore.crypto.cipher
+            )));
+        }
+
+        Ok(keystore)
+    }
+
+    /// Serializes the keystore so it can be written to a file
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// The account number stored with an account's keystore
+    pub fn account_number(&self) -> Option<&str> {
+        self.account_number.as_deref()
+    }
+
+    /// Decrypts the secret
+    ///
+    /// Returns [`Error::IncorrectPassword`] if the password is wrong or the keystore has been modified,
+    /// and [`Error::InvalidKeystore`] if its parameters are malformed or its kdf limits are higher
+    /// than the ones written by [`Keystore::encrypt`]
+    pub fn decrypt(&self, password: &str) -> Result<Zeroizing<Vec<u8>>> {
+        let KeystoreCrypto {
+            kdf_params,
+            nonce,
+            ciphertext,
+            ..
+        } = &self.crypto;
+
+        if kdf_params.opslimit > KDF_OPSLIMIT.0 || kdf_params.memlimit > KDF_MEMLIMIT.0 {
+            return Err(Error::InvalidKeystore(format!(
+                "Kdf limits (opslimit {}, memlimit {}) are higher than the max ({}, {})",
+                kdf_params.opslimit, kdf_params.memlimit, KDF_OPSLIMIT.0, KDF_MEMLIMIT.0
+            )));
+        }
+
+        let salt = Salt::from_slice(&decode_hex("salt", &kdf_params.salt)?)
+            .ok_or_else(|| Error::InvalidKeystore("Invalid salt length".to_string()))?;
+        let nonce = Nonce::from_slice(&decode_hex("nonce", nonce)?)
+            .ok_or_else(|| Error::InvalidKeystore("Invalid nonce length".to_string()))?;
+        let ciphertext = decode_hex("ciphertext", ciphertext)?;
+        if ciphertext.len() < secretbox::MACBYTES {
+            return Err(Error::InvalidKeystore(
+                "Invalid ciphertext length".to_string(),
+            ));
+        }
+
+        let key = derive_key(
+            password,
+            &salt,
+            OpsLimit(kdf_params.opslimit),
+            MemLimit(kdf_params.memlimit),
+        )?;
+
+        secretbox::open(&ciphertext, &nonce, &key)
+            .map(Zeroizing::new)
+            .map_err(|_| Error::IncorrectPassword)
+    }
+}
+
+fn decode_hex(name: &str, value: &str) -> Result<Vec<u8>> {
+    hex::decode(value).map_err(|e| Error::InvalidKeystore(format!("Invalid {}: {}", name, e)))
+}
+
+fn derive_key(password: &str, salt: &Salt, opslimit: OpsLimit, memlimit: MemLimit) -> Result<Key> {
+    let mut key = Key([0; secretbox::KEYBYTES]);
+
+    argon2id13::derive_key(&mut key.0, password.as_bytes(), salt, opslimit, memlimit).map_err(
+        |_| Error::InvalidKeystore("Failed to derive a key from the password".to_string()),
+    )?;
+
+    Ok(key)
+}
+
+#[test]
+fn encrypt_and_decrypt_secret() {
+    let keystore = Keystore::encrypt(KeystoreKind::Account, None, b"secret", "password").unwrap();
+    let json = keystore.to_json().unwrap();
+
+    let keystore = Keystore::from_json(&json, KeystoreKind::Account).unwrap();
+    assert_eq!(keystore.decrypt("password").unwrap().as_slice(), b"secret");
+
+    assert!(matches!(
+        keystore.decrypt("wrong password"),
+        Err(Error::IncorrectPassword)
+    ));
+    assert!(matches!(
+        Keystore::from_json(&json, KeystoreKind::HdWallet),
+        Err(Error::InvalidKeystore(_))
+    ));
+}
+
+#[test]
+fn decrypt_modified_keystore() {
+    let mut keystore =
+        Keystore::encrypt(KeystoreKind::Account, None, b"secret", "password").unwrap();
+
+    let mut ciphertext = hex::decode(&keystore.crypto.ciphertext).unwrap();
+    ciphertext[0] ^= 1;
+    keystore.crypto.ciphertext = hex::encode(ciphertext);
+
+    assert!(matches!(
+        keystore.decrypt("password"),
+        Err(Error::IncorrectPassword)
+    ));
+}
+
+#[test]
+fn unsupported_keystore_version() {
+    let keystore = Keystore::encrypt(KeystoreKind::Account, None, b"secret", "password").unwrap();
+    let json = keystore
+        .to_json()
+        .unwrap()
+        .replace("\"version\": 1", "\"version\": 2");
+
+    assert!(matches!(
+        Keystore::from_json(&json, KeystoreKind::Account),
+        Err(Error::InvalidKeystore(_))
+    ));
+}
+
+#[test]
+fn decrypt_malformed_keystore() {
+    let keystore = Keystore::encrypt(KeystoreKind::Account, None, b"secret", "password").unwrap();
+
+    let mut expensive = keystore.clone();
+    expensive.crypto.kdf_params.memlimit = usize::MAX;
+    assert!(matches!(
+        expensive.decrypt("password"),
+        Err(Error::InvalidKeystore(_))
+    ));
+
+    let mut invalid_salt = keystore.clone();
+    invalid_salt.crypto.kdf_params.salt = "xyz".to_string();
+    assert!(matches!(
+        invalid_salt.decrypt("password"),
+        Err(Error::InvalidKeystore(_))
+    ));
+
+    let mut short_nonce = keystore.clone();
+    short_nonce.crypto.nonce = "abcd".to_string();
+    assert!(matches!(
+        short_nonce.decrypt("password"),
+        Err(Error::InvalidKeystore(_))
+    ));
+
+    let mut short_ciphertext = keystore;
+    short_ciphertext.crypto.ciphertext = "ab".to_string();
+    assert!(matches!(
+        short_ciphertext.decrypt("password"),
+        Err(Error::InvalidKeystore(_))
+    ));
+}
@@ -25,7 +25,7 @@
 //!    let priv_key = "8cf08eb96b00b5a4df86a750bb7ae595a9dbbe91fc091463bfb3d950d5dac467";
 //!    let acc = Account::from_signing_key(priv_key).unwrap();
 //!
-//!    assert_eq!(priv_key, acc.signing_key());
+//!    assert_eq!(priv_key, acc.signing_key().as_str());
 //! ```
 //!
 //!
@@ -67,6 +67,42 @@
 //!
 //! ```
 //!
+//! ## Discover the used accounts of a HD Wallet
+//! - Restoring a HD Wallet from its mnemonic phrase scans the network for the accounts that hold
+//!   coins or have transactions, stopping after a gap of unused addresses
+//!
+//! ```no_run
+//!     use tnb_rs::{HDWallet, DEFAULT_GAP_LIMIT, nodes::RegularNode};
+//!
+//!     #[tokio::main]
+//!     async fn main() {
+//!         let m = "visa nephew like this amazing soldier negative front elevator warfare teach good";
+//!         let hd = HDWallet::from_mnemonic(m, None).unwrap();
+//!
+//!         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+//!         let pv = node.get_pv().await.unwrap();
+//!
+//!         let accounts = hd.discover_accounts(&pv, &node, DEFAULT_GAP_LIMIT).await.unwrap();
+//!     }
+//! ```
+//!
+//! # Keystore
+//! - Store an account's signing key or a HD Wallet's mnemonic phrase in a file encrypted with a password
+//!
+//! ```
+//!     use tnb_rs::{Account, HDWallet};
+//!
+//!     let acc = Account::new();
+//!     let keystore_json = acc.to_keystore("password").unwrap();
+//!
+//!     let acc = Account::from_keystore(&keystore_json, "password").unwrap();
+//!
+//!     let hd = HDWallet::new();
+//!     let keystore_json = hd.to_keystore("password").unwrap();
+//!
+//!     let hd = HDWallet::from_keystore(&keystore_json, "password").unwrap();
+//! ```
+//!
 //! # Signature
 //!
 //! - Creating and verifing a signature
@@ -77,7 +113,7 @@
 //!     let mnemonic = "visa nephew like this amazing soldier negative front elevator warfare teach good";
 //!     let hd = HDWallet::from_mnemonic(mnemonic, None).unwrap();
 //!     
-//!     let acc: Account =  hd.get_first_account();
+//!     let acc: Account =  hd.get_first_account().unwrap();
 //!     let message = "Hidden Message";
 //!
 //!     // Create Signature
@@ -88,7 +124,7 @@
 //!
 //!
 //!     // Verify Signature
-//!     let result = Account::verify_signature(&sig, message, &acc.account_number());
+//!     let result = Account::verify_signature(&sig, message, &acc.account_number()).unwrap();
 //!     
 //!     // The result will only be true if it is validated with the original message and the signer's account_number
 //!     assert_eq!(result, true);
@@ -101,46 +137,108 @@
 //! ```no_run
 //!
 //!     use tnb_rs::{Wallet, HDWallet, Account, models::Transaction};
-//!     
-//!     let mnemonic = "visa nephew like this amazing soldier negative front elevator warfare teach good";
-//!     let hd = HDWallet::from_mnemonic(mnemonic, None).unwrap();
-//!     let acc: Account =  hd.get_first_account();
 //!
-//!     let bank_url = "https://bank.keysign.app";
-//!     let mut wallet = Wallet::new(&acc, bank_url);
-//!     
-//!     // This method retrieves the nodes transaction fee details
-//!     // It is important to call this method before sending a transaction
-//!     wallet.init();
+//!     #[tokio::main]
+//!     async fn main() {
+//!         let mnemonic = "visa nephew like this amazing soldier negative front elevator warfare teach good";
+//!         let hd = HDWallet::from_mnemonic(mnemonic, None).unwrap();
+//!         let acc: Account =  hd.get_first_account().unwrap();
 //!
-//!     let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
-//!     let tx = Transaction::new(recipient, 1000);
+//!         let bank_url = "https://bank.keysign.app";
+//!         let mut wallet = Wallet::new(&acc, bank_url).unwrap();
 //!
-//!     wallet.send_transaction(&tx);
-//!     
-//!     // Sending Multiple transactions
-//!     let recipient2 = "57d7a6e732b6280e967666a76a827bf75a0a34ace8ccbc530422c81f8d7b1239";
-//!     let tx2 = Transaction::new_with_memo(recipient2, 100, "Account Withdrawal");
+//!         // This method retrieves the nodes transaction fee details
+//!         // It is important to call this method before sending a transaction
+//!         wallet.init().await.unwrap();
+//!
+//!         let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+//!         let tx = Transaction::new(recipient, 1000);
+//!
+//!         wallet.send_transaction(&tx).await.unwrap();
 //!
-//!     let txs = vec![tx, tx2];
-//!     wallet.send_transactions(&txs);
+//!         // Sending Multiple transactions
+//!         let recipient2 = "57d7a6e732b6280e967666a76a827bf75a0a34ace8ccbc530422c81f8d7b1239";
+//!         let tx2 = Transaction::new_with_memo(recipient2, 100, "Account Withdrawal");
+//!
+//!         let txs = vec![tx, tx2];
+//!         wallet.send_transactions(&txs).await.unwrap();
+//!     }
 //!
 //! ```
 //!
 //! # Nodes
 //! - Connect directly to the network nodes
-//! ```
+//! ```no_run
 //!     use tnb_rs::nodes::{RegularNode, PrimaryValidator, ConfirmationValidator, ValidatorTrait, ServerNodeTrait};
-//!     
-//!     let cv = ConfirmationValidator::new("http://54.241.48.170");
 //!
-//!     let node = RegularNode::new("https://bank.keysign.app");
+//!     #[tokio::main]
+//!     async fn main() {
+//!         let cv = ConfirmationValidator::new("http://54.241.48.170").unwrap();
+//!
+//!         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+//!
+//!         let pv = PrimaryValidator::new("http://52.52.160.149").unwrap();
+//!
+//!         // or get the selected pv of a node
+//!         let pv: PrimaryValidator = node.get_pv().await.unwrap();
+//!     }
+//!
+//! ```
+//!
+//! # Node failover
+//! - Send requests through a pool of banks. The client switches to the healthiest bank when its
+//!   config is updated and retries requests on the other banks when a bank goes down
+//!
+//! ```no_run
+//!     use tnb_rs::{Account, NodePool, Wallet};
+//!
+//!     #[tokio::main]
+//!     async fn main() {
+//!         let pool = NodePool::new(&["https://bank.keysign.app", "http://54.177.121.3"]).unwrap();
+//!
+//!         let acc = Account::new();
+//!         let mut wallet = Wallet::with_pool(&acc, pool).unwrap();
+//!         wallet.init().await.unwrap();
+//!     }
+//! ```
+//!
+//! # Errors
+//! - Every fallible function returns a [`Result`] with the crate's [`Error`] type
+//!
+//! ```
+//!     use tnb_rs::{Account, Error};
+//!
+//!     let result = Account::from_signing_key("8cf08eb96b00b5a4");
+//!
+//!     assert!(matches!(result, Err(Error::InvalidKeyLength { expected: 64, found: 16 })));
+//! ```
+//!
+//! # Blocking
+//! - The network api is async. Enable the `blocking` feature to use the synchronous
+//!   wrappers in the `blocking` module instead
+//!
+//! ```toml
+//! tnb-rs = { git = "https://github.com/tomijaga/tnb-rs", features = ["blocking"] }
+//! ```
 //!
-//!     let pv = PrimaryValidator::new("http://52.52.160.149");
+//! # Testing
+//! - Enable the `testing` feature to run a local bank and primary validator with an in-memory
+//!   ledger from the `testing` module
 //!
-//!     // or get the selected pv of a node
-//!     let pv: PrimaryValidator = node.get_pv().unwrap();
+//! ```toml
+//! [dev-dependencies]
+//! tnb-rs = { git = "https://github.com/tomijaga/tnb-rs", features = ["testing"] }
+//! ```
+//!
+//! # Command line wallet
+//! - Enable the `cli` feature to build the `tnb` binary for generating keys, checking balances,
+//!   sending coins, listing transactions and signing messages from the terminal
 //!
+//! ```sh
+//! cargo install --git https://github.com/tomijaga/tnb-rs --features cli
+//!
+//! tnb keygen --json
+//! tnb balance --node https://bank.keysign.app <ACCOUNT_NUMBER>
 //! ```
 //!
 
@@ -148,13 +246,13 @@
 #![deny(missing_docs)] // refuse to compile if documentation is missing
 #![cfg_attr(not(test), forbid(unsafe_code))]
 
-#[cfg(any(feature = "std", test))]
-#[macro_use]
-extern crate std;
-
 mod account;
 mod client;
+mod discovery;
+mod error;
 mod hd_wallet;
+mod keystore;
+mod node_pool;
 mod utils;
 mod wallet;
 
@@ -167,8 +265,22 @@ pub mod nodes;
 /// Module with the response of every node's endpoints
 pub mod responses;
 
+pub mod transport;
+
+/// Synchronous wrappers around the async nodes, client and wallet
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+/// Local mock nodes for testing code that talks to the network
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 pub use crate::account::Account;
+pub use crate::client::{BlockStatus, Client};
+pub use crate::discovery::{DiscoveredAccount, DEFAULT_GAP_LIMIT};
+pub use crate::error::{Error, Result};
 pub use crate::hd_wallet::{HDWallet, MAX_CHILD_INDEX};
+pub use crate::node_pool::{NodeHealth, NodePool};
 // pub use models::*;
 pub use wallet::*;
 // pub use responses::*;
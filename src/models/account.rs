@@ -0,0 +1,65 @@
+use crate::models::{PaginatedQueryTrait, SearchParams};
+
+/// Query Builder for a get accounts network request
+///
+/// # Field
+/// - **ordering**: Orders the accounts according to one of their fields, e.g. `-balance`
+/// - **limit**: The max number of accounts to retrieve. The limit can be set to any number between **1** and **100**
+/// - **offset**: The number of accounts to skip
+///
+#[derive(Debug)]
+pub struct AccountQueryBuilder<'a> {
+    params: SearchParams<'a>,
+}
+
+impl<'a> PaginatedQueryTrait<'a> for AccountQueryBuilder<'a> {
+    /// Mutable reference to the hashmap where the data is stored
+    fn get_mut_params(&mut self) -> &mut SearchParams<'a> {
+        &mut self.params
+    }
+}
+
+impl Default for AccountQueryBuilder<'_> {
+    fn default() -> Self {
+        AccountQueryBuilder::new()
+    }
+}
+
+impl<'a> AccountQueryBuilder<'a> {
+    /// Initialize a new account query builder
+    pub fn new() -> Self {
+        AccountQueryBuilder {
+            params: SearchParams::new(),
+        }
+    }
+
+    /// Returns a clone of the hashmap where all the search params are stored
+    pub fn get_params(&self) -> SearchParams<'a> {
+        self.params.clone()
+    }
+
+    /// Orders the accounts according to one of their fields
+    pub fn ordering(&mut self, field: &'a str) -> &mut Self {
+        self.params.insert("ordering", field.to_string());
+        self
+    }
+
+    /// Removes all the data in the query builder
+    pub fn clear(&mut self) {
+        self.params.clear()
+    }
+}
+
+#[test]
+fn account_search_params() {
+    let mut account_query = AccountQueryBuilder::new();
+    account_query.limit(10).offset(20).ordering("-balance");
+
+    let params = account_query.get_params();
+    assert_eq!(params.get("limit"), Some(&"10".to_string()));
+    assert_eq!(params.get("offset"), Some(&"20".to_string()));
+    assert_eq!(params.get("ordering"), Some(&"-balance".to_string()));
+
+    account_query.clear();
+    assert!(account_query.get_params().is_empty());
+}
@@ -0,0 +1,65 @@
+use crate::models::{PaginatedQueryTrait, SearchParams};
+
+/// Query Builder for a get banks network request
+///
+/// # Field
+/// - **ordering**: Orders the banks according to one of their fields, e.g. `-trust`
+/// - **limit**: The max number of banks to retrieve. The limit can be set to any number between **1** and **100**
+/// - **offset**: The number of banks to skip
+///
+#[derive(Debug)]
+pub struct BankQueryBuilder<'a> {
+    params: SearchParams<'a>,
+}
+
+impl<'a> PaginatedQueryTrait<'a> for BankQueryBuilder<'a> {
+    /// Mutable reference to the hashmap where the data is stored
+    fn get_mut_params(&mut self) -> &mut SearchParams<'a> {
+        &mut self.params
+    }
+}
+
+impl Default for BankQueryBuilder<'_> {
+    fn default() -> Self {
+        BankQueryBuilder::new()
+    }
+}
+
+impl<'a> BankQueryBuilder<'a> {
+    /// Initialize a new bank query builder
+    pub fn new() -> Self {
+        BankQueryBuilder {
+            params: SearchParams::new(),
+        }
+    }
+
+    /// Returns a clone of the hashmap where all the search params are stored
+    pub fn get_params(&self) -> SearchParams<'a> {
+        self.params.clone()
+    }
+
+    /// Orders the banks according to one of their fields
+    pub fn ordering(&mut self, field: &'a str) -> &mut Self {
+        self.params.insert("ordering", field.to_string());
+        self
+    }
+
+    /// Removes all the data in the query builder
+    pub fn clear(&mut self) {
+        self.params.clear()
+    }
+}
+
+#[test]
+fn bank_search_params() {
+    let mut bank_query = BankQueryBuilder::new();
+    bank_query.limit(10).offset(20).ordering("-trust");
+
+    let params = bank_query.get_params();
+    assert_eq!(params.get("limit"), Some(&"10".to_string()));
+    assert_eq!(params.get("offset"), Some(&"20".to_string()));
+    assert_eq!(params.get("ordering"), Some(&"-trust".to_string()));
+
+    bank_query.clear();
+    assert!(bank_query.get_params().is_empty());
+}
@@ -1,45 +1,46 @@
-use crate::{account::Account, models::Transaction};
+use crate::{
+    account::Account,
+    error::{Error, Result},
+    models::{PaginatedQueryTrait, SearchParams, Transaction},
+};
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Contains the structure of supported block types
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(untagged)]
-pub enum BlockType<'a> {
+pub enum BlockType {
     /// The Coin Transfer Block Type
     CoinTransfer {
         /// balance key of the sender's account
         balance_key: String,
 
         /// An array of transactions to send to the network
-        txs: Vec<&'a Transaction<'a>>,
+        txs: Vec<Transaction>,
     },
 }
 
-impl BlockType<'_> {
+impl BlockType {
     /// Create a new Coin Transfer BlockType
     /// - This method sorts and formats the transactions so that they can be broadcasted on the network
+    /// - Returns an error if a transaction's memo contains characters the network does not accept
+    pub fn coin_transfer(balance_lock: String, mut txs: Vec<&Transaction>) -> Result<BlockType> {
+        txs.sort_by(|a, b| a.recipient.cmp(&b.recipient));
 
-    pub fn coin_transfer<'a>(
-        balance_lock: String,
-        mut txs: Vec<&'a Transaction<'a>>,
-    ) -> BlockType<'a> {
-        txs.sort_by(|a, b| a.recipient.cmp(b.recipient));
-
-        let re = Regex::new(r"^[a-zA-Z0-9_ ]*$").unwrap();
+        let re = Regex::new(r"^[a-zA-Z0-9_ ]*$").expect("memo regex is valid");
 
         for tx in txs.iter() {
-            if tx.memo.is_some() {
-                if !re.is_match(tx.memo.unwrap()) {
-                    panic!("Memo can only contain alphanumeric values (Aa - Zz, 0 - 9), space and an underscore (_)");
+            if let Some(memo) = &tx.memo {
+                if !re.is_match(memo) {
+                    return Err(Error::InvalidMemo(memo.to_string()));
                 }
             }
         }
 
-        BlockType::CoinTransfer {
+        Ok(BlockType::CoinTransfer {
             balance_key: balance_lock,
-            txs: txs,
-        }
+            txs: txs.into_iter().cloned().collect(),
+        })
     }
 }
 
@@ -49,9 +50,9 @@ fn test_regex() {
     let valid_memo = "Testing Regex";
     let invalid_memo = "!@#$%^&*(";
 
-    assert_eq!(re.is_match(valid_memo), true);
-    assert_eq!(re.is_match(invalid_memo), false);
-    assert_eq!(re.is_match("Testing Regex!"), false);
+    assert!(re.is_match(valid_memo));
+    assert!(!re.is_match(invalid_memo));
+    assert!(!re.is_match("Testing Regex!"));
 }
 
 #[test]
@@ -60,40 +61,195 @@ fn coin_transfer_block_with_valid_memo() {
     let balance_lock = rand_acc.account_number().to_string();
 
     let transaction = Transaction {
-        recipient: rand_acc.account_number(),
+        recipient: rand_acc.account_number().to_string(),
         fee: None,
         amount: 1,
-        memo: Some("_Testing Rust library"),
+        memo: Some("_Testing Rust library".to_string()),
     };
 
-    BlockType::coin_transfer(balance_lock, vec![&transaction]);
+    assert!(BlockType::coin_transfer(balance_lock, vec![&transaction]).is_ok());
 }
 
 #[test]
-#[should_panic]
 fn coin_transfer_block_with_invalid_memo() {
     let rand_acc = Account::new();
     let balance_lock = rand_acc.account_number().to_string();
 
     let transaction = Transaction {
-        recipient: rand_acc.account_number(),
+        recipient: rand_acc.account_number().to_string(),
         fee: None,
         amount: 1,
-        memo: Some("_Testing Rust library!"),
+        memo: Some("_Testing Rust library!".to_string()),
     };
 
-    BlockType::coin_transfer(balance_lock, vec![&transaction]);
+    assert!(matches!(
+        BlockType::coin_transfer(balance_lock, vec![&transaction]),
+        Err(Error::InvalidMemo(memo)) if memo == "_Testing Rust library!"
+    ));
 }
 
 /// Block structure to make a block request on the network
-#[derive(Debug, Serialize)]
-pub struct BlockMessage<'a> {
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BlockMessage {
     /// sender's account number
-    pub account_number: &'a str,
+    pub account_number: String,
 
     /// block message that contains the sender's request
-    pub message: &'a BlockType<'a>,
+    pub message: BlockType,
 
     /// the signed message
     pub signature: String,
 }
+
+impl BlockMessage {
+    /// Verifies that the block message was signed by the sender's account number
+    ///
+    /// The message is serialized the same way [`Account::create_block_message`](crate::Account::create_block_message) serializes it before signing
+    ///
+    /// # Example
+    /// ```
+    ///     use tnb_rs::{Account, models::{BlockMessage, BlockType, Transaction}};
+    ///
+    ///     let acc = Account::new();
+    ///     let tx = Transaction::new("1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224", 1);
+    ///     let block = BlockType::coin_transfer(acc.account_number().to_string(), vec![&tx]).unwrap();
+    ///
+    ///     let json = serde_json::to_string(&acc.create_block_message(&block).unwrap()).unwrap();
+    ///
+    ///     // parse a block that was posted to the node
+    ///     let block_message: BlockMessage = serde_json::from_str(&json).unwrap();
+    ///
+    ///     assert!(block_message.verify().unwrap());
+    /// ```
+    pub fn verify(&self) -> Result<bool> {
+        let serialized_message = serde_json::to_string(&self.message)?;
+        Account::verify_signature(&self.signature, &serialized_message, &self.account_number)
+    }
+}
+
+#[test]
+fn deserialize_and_verify_block_message() {
+    let acc = Account::new();
+    let tx = Transaction::new_with_memo(acc.account_number(), 5, "Testing Rust library");
+    let block = BlockType::coin_transfer(acc.account_number().to_string(), vec![&tx]).unwrap();
+
+    let json = serde_json::to_string(&acc.create_block_message(&block).unwrap()).unwrap();
+
+    let mut block_message: BlockMessage = serde_json::from_str(&json).unwrap();
+    assert_eq!(block_message.message, block);
+    assert!(block_message.verify().unwrap());
+
+    block_message.account_number = Account::new().account_number().to_string();
+    assert!(!block_message.verify().unwrap());
+}
+
+#[test]
+fn verify_tampered_block_message() {
+    let acc = Account::new();
+    let tx = Transaction::new(acc.account_number(), 5);
+    let block = BlockType::coin_transfer(acc.account_number().to_string(), vec![&tx]).unwrap();
+
+    let mut block_message = acc.create_block_message(&block).unwrap();
+
+    let BlockType::CoinTransfer { txs, .. } = &mut block_message.message;
+    txs[0].amount = 500;
+
+    assert!(!block_message.verify().unwrap());
+}
+
+/// Query Builder for a get blocks network request
+///
+/// # Field
+/// - **sender**: Searches for blocks sent by the given account
+/// - **balance_key**: Searches for the block that used the given balance key
+/// - **signature**: Searches for the block with the given signature
+/// - **ordering**: Orders the blocks according to one of their fields, e.g. `-created_date`
+/// - **limit**: The max number of blocks to retrieve. The limit can be set to any number between **1** and **100**
+/// - **offset**: The number of blocks to skip
+///
+#[derive(Debug)]
+pub struct BlockQueryBuilder<'a> {
+    params: SearchParams<'a>,
+}
+
+impl<'a> PaginatedQueryTrait<'a> for BlockQueryBuilder<'a> {
+    /// Mutable reference to the hashmap where the data is stored
+    fn get_mut_params(&mut self) -> &mut SearchParams<'a> {
+        &mut self.params
+    }
+}
+
+impl Default for BlockQueryBuilder<'_> {
+    fn default() -> Self {
+        BlockQueryBuilder::new()
+    }
+}
+
+impl<'a> BlockQueryBuilder<'a> {
+    /// Initialize a new block query builder
+    pub fn new() -> Self {
+        BlockQueryBuilder {
+            params: SearchParams::new(),
+        }
+    }
+
+    /// Returns a clone of the hashmap where all the search params are stored
+    pub fn get_params(&self) -> SearchParams<'a> {
+        self.params.clone()
+    }
+
+    /// Searches for blocks sent by the given account
+    pub fn sender(&mut self, account_number: &'a str) -> &mut Self {
+        self.params.insert("sender", account_number.to_string());
+        self
+    }
+
+    /// Searches for the block that used the given balance key
+    pub fn balance_key(&mut self, balance_key: &'a str) -> &mut Self {
+        self.params.insert("balance_key", balance_key.to_string());
+        self
+    }
+
+    /// Searches for the block with the given signature
+    pub fn signature(&mut self, signature: &'a str) -> &mut Self {
+        self.params.insert("signature", signature.to_string());
+        self
+    }
+
+    /// Orders the blocks according to one of their fields
+    pub fn ordering(&mut self, field: &'a str) -> &mut Self {
+        self.params.insert("ordering", field.to_string());
+        self
+    }
+
+    /// Removes all the data in the query builder
+    pub fn clear(&mut self) {
+        self.params.clear()
+    }
+}
+
+#[test]
+fn block_search_params() {
+    let acc = Account::new();
+    let mut block_query = BlockQueryBuilder::new();
+
+    block_query
+        .sender(acc.account_number())
+        .balance_key(acc.account_number())
+        .signature("abcd")
+        .ordering("-created_date")
+        .limit(5);
+
+    let params = block_query.get_params();
+    assert_eq!(
+        params.get("sender"),
+        Some(&acc.account_number().to_string())
+    );
+    assert_eq!(
+        params.get("balance_key"),
+        Some(&acc.account_number().to_string())
+    );
+    assert_eq!(params.get("signature"), Some(&"abcd".to_string()));
+    assert_eq!(params.get("ordering"), Some(&"-created_date".to_string()));
+    assert_eq!(params.get("limit"), Some(&"5".to_string()));
+}
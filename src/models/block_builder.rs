@@ -0,0 +1,192 @@
+use crate::{
+    account::Account,
+    error::{Error, Result},
+    models::{BlockMessage, BlockType, NodeType, Transaction},
+};
+
+/// Builds and signs a coin transfer block without connecting to a node
+///
+/// The balance lock and the fees of the regular node and primary validator are usually retrieved
+/// from the network. The builder takes them as explicit inputs instead, so blocks can be signed
+/// on an offline machine and broadcasted later with
+/// [`RegularNode::submit_signed_block`](crate::nodes::RegularNode::submit_signed_block)
+///
+/// # Example
+/// ```
+///     use tnb_rs::{Account, models::{BlockBuilder, Transaction}};
+///
+///     let acc = Account::new();
+///     let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+///
+///     let block = BlockBuilder::new(&acc)
+///         .balance_lock(acc.account_number())
+///         .bank_fee("29865762fae7d26e51f6465b3fea436d513478cfb8aa068e88a927e887cdc5fc", 1)
+///         .primary_validator_fee("ec8f6734272e8d9d5ea995479dd6d173424be38b313a3069d5fa62e7038a08e9", 1)
+///         .transaction(Transaction::new(recipient, 1000))
+///         .build()
+///         .unwrap();
+///
+///     // The signed block can be stored and broadcasted from another machine
+///     let json = serde_json::to_string(&block).unwrap();
+///     println!("signed block: {}", json);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct BlockBuilder<'a> {
+    account: &'a Account,
+    balance_lock: Option<String>,
+    bank_fee: Option<Transaction>,
+    pv_fee: Option<Transaction>,
+    txs: Vec<Transaction>,
+}
+
+impl<'a> BlockBuilder<'a> {
+    /// Create a new block builder that signs the block with the given account
+    pub fn new(account: &'a Account) -> Self {
+        BlockBuilder {
+            account,
+            balance_lock: None,
+            bank_fee: None,
+            pv_fee: None,
+            txs: Vec::new(),
+        }
+    }
+
+    /// Sets the balance lock of the sender's account
+    pub fn balance_lock(&mut self, balance_lock: &str) -> &mut Self {
+        self.balance_lock = Some(balance_lock.to_string());
+        self
+    }
+
+    /// Sets the account number and the amount of the regular node's fee
+    pub fn bank_fee(&mut self, recipient: &str, amount: u64) -> &mut Self {
+        self.bank_fee = Some(Transaction {
+            recipient: recipient.to_string(),
+            amount,
+            fee: Some(NodeType::BANK),
+            memo: None,
+        });
+        self
+    }
+
+    /// Sets the account number and the amount of the primary validator's fee
+    pub fn primary_validator_fee(&mut self, recipient: &str, amount: u64) -> &mut Self {
+        self.pv_fee = Some(Transaction {
+            recipient: recipient.to_string(),
+            amount,
+            fee: Some(NodeType::PRIMARY_VALIDATOR),
+            memo: None,
+        });
+        self
+    }
+
+    /// Adds a transaction to the block
+    pub fn transaction(&mut self, tx: Transaction) -> &mut Self {
+        self.txs.push(tx);
+        self
+    }
+
+    /// Adds multiple transactions to the block
+    pub fn transactions(&mut self, txs: &[Transaction]) -> &mut Self {
+        self.txs.extend_from_slice(txs);
+        self
+    }
+
+    /// Creates the coin transfer block and signs it with the builder's account
+    ///
+    /// Returns an error if the balance lock or one of the fees has not been set
+    pub fn build(&self) -> Result<BlockMessage> {
+        let balance_lock = self
+            .balance_lock
+            .clone()
+            .ok_or(Error::IncompleteBlock("balance lock"))?;
+        let bank_fee = self
+            .bank_fee
+            .as_ref()
+            .ok_or(Error::IncompleteBlock("bank fee"))?;
+        let pv_fee = self
+            .pv_fee
+            .as_ref()
+            .ok_or(Error::IncompleteBlock("primary validator fee"))?;
+
+        let mut txs: Vec<&Transaction> = self.txs.iter().collect();
+        txs.push(bank_fee);
+        txs.push(pv_fee);
+
+        let block = BlockType::coin_transfer(balance_lock, txs)?;
+
+        self.account.create_block_message(&block)
+    }
+}
+
+#[cfg(test)]
+const BANK_ACCOUNT_NUMBER: &str =
+    "29865762fae7d26e51f6465b3fea436d513478cfb8aa068e88a927e887cdc5fc";
+
+#[cfg(test)]
+const PV_ACCOUNT_NUMBER: &str = "ec8f6734272e8d9d5ea995479dd6d173424be38b313a3069d5fa62e7038a08e9";
+
+#[test]
+fn build_signed_block_offline() {
+    let acc = Account::new();
+    let recipient = Account::new();
+
+    let block = BlockBuilder::new(&acc)
+        .balance_lock(acc.account_number())
+        .bank_fee(BANK_ACCOUNT_NUMBER, 1)
+        .primary_validator_fee(PV_ACCOUNT_NUMBER, 2)
+        .transaction(Transaction::new_with_memo(
+            recipient.account_number(),
+            100,
+            "Offline",
+        ))
+        .build()
+        .unwrap();
+
+    assert_eq!(block.account_number, acc.account_number());
+
+    let BlockType::CoinTransfer { balance_key, txs } = &block.message;
+    assert_eq!(balance_key, acc.account_number());
+    assert_eq!(txs.len(), 3);
+    assert!(txs.windows(2).all(|w| w[0].recipient <= w[1].recipient));
+    assert!(txs
+        .iter()
+        .any(|tx| tx.fee == Some(NodeType::BANK) && tx.recipient == BANK_ACCOUNT_NUMBER));
+    assert!(
+        txs.iter()
+            .any(|tx| tx.fee == Some(NodeType::PRIMARY_VALIDATOR)
+                && tx.recipient == PV_ACCOUNT_NUMBER)
+    );
+
+    let serialized_message = serde_json::to_string(&block.message).unwrap();
+    assert!(
+        Account::verify_signature(&block.signature, &serialized_message, acc.account_number())
+            .unwrap()
+    );
+}
+
+#[test]
+fn build_block_with_missing_fields() {
+    let acc = Account::new();
+    let mut builder = BlockBuilder::new(&acc);
+
+    assert!(matches!(
+        builder.build(),
+        Err(Error::IncompleteBlock("balance lock"))
+    ));
+
+    builder.balance_lock(acc.account_number());
+    assert!(matches!(
+        builder.build(),
+        Err(Error::IncompleteBlock("bank fee"))
+    ));
+
+    builder.bank_fee(BANK_ACCOUNT_NUMBER, 1);
+    assert!(matches!(
+        builder.build(),
+        Err(Error::IncompleteBlock("primary validator fee"))
+    ));
+
+    builder.primary_validator_fee(PV_ACCOUNT_NUMBER, 1);
+    assert!(builder.build().is_ok());
+}
@@ -0,0 +1,163 @@
+use crate::models::{PaginatedQueryTrait, SearchParams};
+
+/// Query Builder for a get confirmation blocks network request
+///
+/// # Field
+/// - **block**: Searches for the confirmations of the block with the given id
+/// - **block_identifier**: Searches for the confirmations with the given block identifier
+/// - **validator**: Searches for the confirmations sent by the validator with the given id
+/// - **ordering**: Orders the confirmation blocks according to one of their fields, e.g. `-created_date`
+/// - **limit**: The max number of confirmation blocks to retrieve. The limit can be set to any number between **1** and **100**
+/// - **offset**: The number of confirmation blocks to skip
+///
+#[derive(Debug)]
+pub struct ConfirmationBlockQueryBuilder<'a> {
+    params: SearchParams<'a>,
+}
+
+impl<'a> PaginatedQueryTrait<'a> for ConfirmationBlockQueryBuilder<'a> {
+    /// Mutable reference to the hashmap where the data is stored
+    fn get_mut_params(&mut self) -> &mut SearchParams<'a> {
+        &mut self.params
+    }
+}
+
+impl Default for ConfirmationBlockQueryBuilder<'_> {
+    fn default() -> Self {
+        ConfirmationBlockQueryBuilder::new()
+    }
+}
+
+impl<'a> ConfirmationBlockQueryBuilder<'a> {
+    /// Initialize a new confirmation block query builder
+    pub fn new() -> Self {
+        ConfirmationBlockQueryBuilder {
+            params: SearchParams::new(),
+        }
+    }
+
+    /// Returns a clone of the hashmap where all the search params are stored
+    pub fn get_params(&self) -> SearchParams<'a> {
+        self.params.clone()
+    }
+
+    /// Searches for the confirmations of the block with the given id
+    pub fn block(&mut self, block_id: &'a str) -> &mut Self {
+        self.params.insert("block", block_id.to_string());
+        self
+    }
+
+    /// Searches for the confirmations with the given block identifier
+    pub fn block_identifier(&mut self, block_identifier: &'a str) -> &mut Self {
+        self.params
+            .insert("block_identifier", block_identifier.to_string());
+        self
+    }
+
+    /// Searches for the confirmations sent by the validator with the given id
+    pub fn validator(&mut self, validator_id: &'a str) -> &mut Self {
+        self.params.insert("validator", validator_id.to_string());
+        self
+    }
+
+    /// Orders the confirmation blocks according to one of their fields
+    pub fn ordering(&mut self, field: &'a str) -> &mut Self {
+        self.params.insert("ordering", field.to_string());
+        self
+    }
+
+    /// Removes all the data in the query builder
+    pub fn clear(&mut self) {
+        self.params.clear()
+    }
+}
+
+/// Query Builder for a get invalid blocks network request
+///
+/// # Field
+/// - **block**: Searches for the rejections of the block with the given id
+/// - **block_identifier**: Searches for the rejections with the given block identifier
+/// - **ordering**: Orders the invalid blocks according to one of their fields, e.g. `-created_date`
+/// - **limit**: The max number of invalid blocks to retrieve. The limit can be set to any number between **1** and **100**
+/// - **offset**: The number of invalid blocks to skip
+///
+#[derive(Debug)]
+pub struct InvalidBlockQueryBuilder<'a> {
+    params: SearchParams<'a>,
+}
+
+impl<'a> PaginatedQueryTrait<'a> for InvalidBlockQueryBuilder<'a> {
+    /// Mutable reference to the hashmap where the data is stored
+    fn get_mut_params(&mut self) -> &mut SearchParams<'a> {
+        &mut self.params
+    }
+}
+
+impl Default for InvalidBlockQueryBuilder<'_> {
+    fn default() -> Self {
+        InvalidBlockQueryBuilder::new()
+    }
+}
+
+impl<'a> InvalidBlockQueryBuilder<'a> {
+    /// Initialize a new invalid block query builder
+    pub fn new() -> Self {
+        InvalidBlockQueryBuilder {
+            params: SearchParams::new(),
+        }
+    }
+
+    /// Returns a clone of the hashmap where all the search params are stored
+    pub fn get_params(&self) -> SearchParams<'a> {
+        self.params.clone()
+    }
+
+    /// Searches for the rejections of the block with the given id
+    pub fn block(&mut self, block_id: &'a str) -> &mut Self {
+        self.params.insert("block", block_id.to_string());
+        self
+    }
+
+    /// Searches for the rejections with the given block identifier
+    pub fn block_identifier(&mut self, block_identifier: &'a str) -> &mut Self {
+        self.params
+            .insert("block_identifier", block_identifier.to_string());
+        self
+    }
+
+    /// Orders the invalid blocks according to one of their fields
+    pub fn ordering(&mut self, field: &'a str) -> &mut Self {
+        self.params.insert("ordering", field.to_string());
+        self
+    }
+
+    /// Removes all the data in the query builder
+    pub fn clear(&mut self) {
+        self.params.clear()
+    }
+}
+
+#[test]
+fn confirmation_block_search_params() {
+    let mut query = ConfirmationBlockQueryBuilder::new();
+    query
+        .block("a7b5c4d2")
+        .block_identifier("ffff")
+        .validator("b4c3")
+        .ordering("-created_date")
+        .offset(10);
+
+    let params = query.get_params();
+    assert_eq!(params.get("block"), Some(&"a7b5c4d2".to_string()));
+    assert_eq!(params.get("block_identifier"), Some(&"ffff".to_string()));
+    assert_eq!(params.get("validator"), Some(&"b4c3".to_string()));
+    assert_eq!(params.get("ordering"), Some(&"-created_date".to_string()));
+    assert_eq!(params.get("offset"), Some(&"10".to_string()));
+
+    let mut query = InvalidBlockQueryBuilder::new();
+    query.block("a7b5c4d2").limit(1);
+
+    let params = query.get_params();
+    assert_eq!(params.get("block"), Some(&"a7b5c4d2".to_string()));
+    assert_eq!(params.get("limit"), Some(&"1".to_string()));
+}
@@ -1,9 +1,21 @@
+mod account;
+mod bank;
 mod block;
+mod block_builder;
+mod confirmation_block;
 mod query;
 mod signed_message;
 mod transaction;
+mod trust;
+mod validator;
 
+pub use account::*;
+pub use bank::*;
 pub use block::*;
+pub use block_builder::*;
+pub use confirmation_block::*;
 pub use query::*;
 pub use signed_message::*;
 pub use transaction::*;
+pub use trust::*;
+pub use validator::*;
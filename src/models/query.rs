@@ -3,6 +3,29 @@ use std::collections::HashMap;
 /// Data storage type for query builders
 pub type SearchParams<'a> = HashMap<&'a str, String>;
 
+/// Options for iterating over every page of a paginated endpoint
+///
+/// # Example
+/// ```
+///     use tnb_rs::models::PaginationOptions;
+///
+///     // request 20 items per page and stop after the first 100 items
+///     let options = PaginationOptions {
+///         page_size: Some(20),
+///         max_items: Some(100),
+///     };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaginationOptions {
+    /// The number of items requested per page
+    /// - The node's default page size is used if it is `None`
+    /// - Overrides the `limit` set on the query
+    pub page_size: Option<u8>,
+
+    /// The max number of items to retrieve before the iteration stops
+    pub max_items: Option<usize>,
+}
+
 /// Trait for endpoints that return paginated data
 pub trait PaginatedQueryTrait<'a> {
     /// Get a reference to the data store of
@@ -1,7 +1,17 @@
+use crate::{
+    account::Account,
+    error::{Error, Result},
+};
 use serde::{Deserialize, Serialize};
 
 /// Enum for Supported Node Requests
-#[derive(Debug, Deserialize, Serialize)]
+///
+/// The variants are serialized without a tag because the node knows what the message is for from
+/// the endpoint it was sent to. Variants with the same shape are deserialized as the first of
+/// them by serde: the trust requests as [`ChainData::UpdateAccountTrust`] and primary validator
+/// updates as [`ChainData::ConnectionRequest`]. Use [`ChainData::parse`] or
+/// [`SignedMessage::parse`] with the [`ChainDataKind`] of the endpoint to get the right variant.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum ChainData {
     /// Request structure for changing an account's trust
@@ -9,17 +19,431 @@ pub enum ChainData {
         /// account's trust
         trust: i32,
     },
+
+    /// Request structure for changing another bank's trust
+    UpdateBankTrust {
+        /// bank's trust
+        trust: i32,
+    },
+
+    /// Request structure for changing a validator's trust
+    UpdateValidatorTrust {
+        /// validator's trust
+        trust: i32,
+    },
+
+    /// Request structure for connecting a node to another node
+    ConnectionRequest {
+        /// ip address of the node requesting the connection
+        ip_address: String,
+
+        /// port the node requesting the connection listens on
+        port: u16,
+
+        /// protocol of the node requesting the connection, `http` or `https`
+        protocol: String,
+    },
+
+    /// Request structure for telling a bank that the sending bank switched to a new primary validator
+    PrimaryValidatorUpdated {
+        /// ip address of the new primary validator
+        ip_address: String,
+
+        /// port the new primary validator listens on
+        port: u16,
+
+        /// protocol of the new primary validator, `http` or `https`
+        protocol: String,
+    },
+
+    /// Request structure for asking a confirmation validator to become the primary validator
+    UpgradeRequest {
+        /// node identifier of the validator that should become the primary validator
+        validator_node_identifier: String,
+    },
+
+    /// Request structure for starting or stopping a crawl of the network
+    Crawl {
+        /// whether to start or stop the crawl
+        crawl: MaintenanceCommand,
+    },
+
+    /// Request structure for starting or stopping a clean of the node's stale banks and validators
+    Clean {
+        /// whether to start or stop the clean
+        clean: MaintenanceCommand,
+    },
+}
+
+/// The kind of request a [`ChainData`] message is, which a node knows from the endpoint it received
+/// the message on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainDataKind {
+    /// [`ChainData::UpdateAccountTrust`], sent to `/accounts/<account_number>`
+    UpdateAccountTrust,
+
+    /// [`ChainData::UpdateBankTrust`], sent to `/banks/<node_identifier>`
+    UpdateBankTrust,
+
+    /// [`ChainData::UpdateValidatorTrust`], sent to `/validators/<node_identifier>`
+    UpdateValidatorTrust,
+
+    /// [`ChainData::ConnectionRequest`], sent to `/connection_requests`
+    ConnectionRequest,
+
+    /// [`ChainData::PrimaryValidatorUpdated`], sent to `/primary_validator_updated`
+    PrimaryValidatorUpdated,
+
+    /// [`ChainData::UpgradeRequest`], sent to `/upgrade_request`
+    UpgradeRequest,
+
+    /// [`ChainData::Crawl`], sent to `/crawl`
+    Crawl,
+
+    /// [`ChainData::Clean`], sent to `/clean`
+    Clean,
+}
+
+/// Starts or stops one of a node's maintenance tasks, like a crawl or a clean
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MaintenanceCommand {
+    /// Start the task
+    Start,
+
+    /// Stop the running task
+    Stop,
+}
+
+/// The highest trust a node can give to an account or another node
+pub const MAX_TRUST: i32 = 100;
+
+impl ChainData {
+    /// Creates a request to change an account's trust
+    ///
+    /// Returns an error if the trust is not between 0 and [`MAX_TRUST`]
+    pub fn update_account_trust(trust: i32) -> Result<Self> {
+        Ok(ChainData::UpdateAccountTrust {
+            trust: check_trust(trust)?,
+        })
+    }
+
+    /// Creates a request to change another bank's trust
+    ///
+    /// Returns an error if the trust is not between 0 and [`MAX_TRUST`]
+    pub fn update_bank_trust(trust: i32) -> Result<Self> {
+        Ok(ChainData::UpdateBankTrust {
+            trust: check_trust(trust)?,
+        })
+    }
+
+    /// Creates a request to change a validator's trust
+    ///
+    /// Returns an error if the trust is not between 0 and [`MAX_TRUST`]
+    pub fn update_validator_trust(trust: i32) -> Result<Self> {
+        Ok(ChainData::UpdateValidatorTrust {
+            trust: check_trust(trust)?,
+        })
+    }
+
+    /// Creates a request to start crawling the network for new nodes
+    pub fn start_crawl() -> Self {
+        ChainData::Crawl {
+            crawl: MaintenanceCommand::Start,
+        }
+    }
+
+    /// Creates a request to stop the running crawl
+    pub fn stop_crawl() -> Self {
+        ChainData::Crawl {
+            crawl: MaintenanceCommand::Stop,
+        }
+    }
+
+    /// Creates a request to start removing the banks and validators that are no longer online
+    pub fn start_clean() -> Self {
+        ChainData::Clean {
+            clean: MaintenanceCommand::Start,
+        }
+    }
+
+    /// Creates a request to stop the running clean
+    pub fn stop_clean() -> Self {
+        ChainData::Clean {
+            clean: MaintenanceCommand::Stop,
+        }
+    }
+
+    /// Parses a message received on the endpoint of the kind
+    ///
+    /// Returns an error if the message does not have the shape of the kind
+    ///
+    /// # Example
+    /// ```
+    ///     use tnb_rs::models::{ChainData, ChainDataKind};
+    ///
+    ///     let json = r#"{"ip_address":"10.0.0.1","port":80,"protocol":"http"}"#;
+    ///     let message = ChainData::parse(json, ChainDataKind::PrimaryValidatorUpdated).unwrap();
+    ///
+    ///     assert_eq!(message.kind(), ChainDataKind::PrimaryValidatorUpdated);
+    /// ```
+    pub fn parse(json: &str, kind: ChainDataKind) -> Result<Self> {
+        serde_json::from_str::<ChainData>(json)?.into_kind(kind)
+    }
+
+    /// The kind of request the message is
+    pub fn kind(&self) -> ChainDataKind {
+        match self {
+            ChainData::UpdateAccountTrust { .. } => ChainDataKind::UpdateAccountTrust,
+            ChainData::UpdateBankTrust { .. } => ChainDataKind::UpdateBankTrust,
+            ChainData::UpdateValidatorTrust { .. } => ChainDataKind::UpdateValidatorTrust,
+            ChainData::ConnectionRequest { .. } => ChainDataKind::ConnectionRequest,
+            ChainData::PrimaryValidatorUpdated { .. } => ChainDataKind::PrimaryValidatorUpdated,
+            ChainData::UpgradeRequest { .. } => ChainDataKind::UpgradeRequest,
+            ChainData::Crawl { .. } => ChainDataKind::Crawl,
+            ChainData::Clean { .. } => ChainDataKind::Clean,
+        }
+    }
+
+    /// Converts a message to the variant of the kind that has the same shape
+    fn into_kind(self, kind: ChainDataKind) -> Result<Self> {
+        let data = match (kind, self.trust(), self) {
+            (ChainDataKind::UpdateAccountTrust, Some(trust), _) => {
+                ChainData::UpdateAccountTrust { trust }
+            }
+            (ChainDataKind::UpdateBankTrust, Some(trust), _) => {
+                ChainData::UpdateBankTrust { trust }
+            }
+            (ChainDataKind::UpdateValidatorTrust, Some(trust), _) => {
+                ChainData::UpdateValidatorTrust { trust }
+            }
+            (
+                ChainDataKind::PrimaryValidatorUpdated,
+                _,
+                ChainData::ConnectionRequest {
+                    ip_address,
+                    port,
+                    protocol,
+                },
+            ) => ChainData::PrimaryValidatorUpdated {
+                ip_address,
+                port,
+                protocol,
+            },
+            (
+                ChainDataKind::ConnectionRequest,
+                _,
+                ChainData::PrimaryValidatorUpdated {
+                    ip_address,
+                    port,
+                    protocol,
+                },
+            ) => ChainData::ConnectionRequest {
+                ip_address,
+                port,
+                protocol,
+            },
+            (kind, _, data) if data.kind() == kind => data,
+            (kind, _, _) => return Err(Error::UnexpectedMessage(kind)),
+        };
+
+        Ok(data)
+    }
+
+    /// The trust requested by the message, or `None` if it is not a trust request
+    pub fn trust(&self) -> Option<i32> {
+        match self {
+            ChainData::UpdateAccountTrust { trust }
+            | ChainData::UpdateBankTrust { trust }
+            | ChainData::UpdateValidatorTrust { trust } => Some(*trust),
+            ChainData::ConnectionRequest { .. }
+            | ChainData::PrimaryValidatorUpdated { .. }
+            | ChainData::UpgradeRequest { .. }
+            | ChainData::Crawl { .. }
+            | ChainData::Clean { .. } => None,
+        }
+    }
+}
+
+fn check_trust(trust: i32) -> Result<i32> {
+    if !(0..=MAX_TRUST).contains(&trust) {
+        return Err(Error::InvalidTrust(trust));
+    }
+
+    Ok(trust)
 }
 
 /// Structure for making Node requests to the network
-#[derive(Debug, Serialize)]
-pub struct SignedMessage<'a> {
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SignedMessage {
     /// message that contains the node's request
-    pub message: &'a ChainData,
+    pub message: ChainData,
 
     /// The node's identification number
-    pub node_identifier: &'a str,
+    pub node_identifier: String,
 
     /// thh signed message
     pub signature: String,
 }
+
+impl SignedMessage {
+    /// Parses a signed message received on the endpoint of the kind
+    ///
+    /// Returns an error if the message does not have the shape of the kind
+    pub fn parse(json: &str, kind: ChainDataKind) -> Result<Self> {
+        let signed_message: SignedMessage = serde_json::from_str(json)?;
+
+        Ok(SignedMessage {
+            message: signed_message.message.into_kind(kind)?,
+            ..signed_message
+        })
+    }
+
+    /// Verifies that the message was signed by the node's identifier
+    ///
+    /// The message is serialized the same way [`Account::create_signed_message`](crate::Account::create_signed_message) serializes it before signing
+    pub fn verify(&self) -> Result<bool> {
+        let serialized_message = serde_json::to_string(&self.message)?;
+        Account::verify_signature(&self.signature, &serialized_message, &self.node_identifier)
+    }
+}
+
+#[test]
+fn deserialize_and_verify_signed_message() {
+    let node = Account::new();
+    let data = ChainData::UpdateAccountTrust { trust: 42 };
+
+    let json = serde_json::to_string(&node.create_signed_message(&data).unwrap()).unwrap();
+
+    let mut signed_message: SignedMessage = serde_json::from_str(&json).unwrap();
+    assert_eq!(signed_message.message, data);
+    assert!(signed_message.verify().unwrap());
+
+    signed_message.message = ChainData::UpdateAccountTrust { trust: 100 };
+    assert!(!signed_message.verify().unwrap());
+}
+
+#[test]
+fn trust_out_of_range() {
+    assert_eq!(
+        ChainData::update_account_trust(100).unwrap(),
+        ChainData::UpdateAccountTrust { trust: 100 }
+    );
+    assert!(ChainData::update_account_trust(0).is_ok());
+
+    assert!(matches!(
+        ChainData::update_account_trust(101),
+        Err(Error::InvalidTrust(101))
+    ));
+    assert!(matches!(
+        ChainData::update_account_trust(-1),
+        Err(Error::InvalidTrust(-1))
+    ));
+}
+
+#[test]
+fn node_trust_requests() {
+    let data = ChainData::update_validator_trust(75).unwrap();
+    assert_eq!(data, ChainData::UpdateValidatorTrust { trust: 75 });
+    assert_eq!(serde_json::to_string(&data).unwrap(), r#"{"trust":75}"#);
+
+    // the node knows what the trust is for from the endpoint
+    let signed_message: SignedMessage = serde_json::from_str(
+        &serde_json::to_string(&Account::new().create_signed_message(&data).unwrap()).unwrap(),
+    )
+    .unwrap();
+    assert!(signed_message.verify().unwrap());
+    assert_eq!(signed_message.message.trust(), Some(75));
+
+    let json = serde_json::to_string(&signed_message).unwrap();
+    let parsed = SignedMessage::parse(&json, ChainDataKind::UpdateValidatorTrust).unwrap();
+    assert_eq!(parsed.message, data);
+    assert!(parsed.verify().unwrap());
+
+    assert_eq!(
+        ChainData::parse(r#"{"trust":60}"#, ChainDataKind::UpdateBankTrust).unwrap(),
+        ChainData::UpdateBankTrust { trust: 60 }
+    );
+    assert!(matches!(
+        ChainData::parse(r#"{"crawl":"start"}"#, ChainDataKind::UpdateBankTrust),
+        Err(Error::UnexpectedMessage(ChainDataKind::UpdateBankTrust))
+    ));
+
+    assert!(matches!(
+        ChainData::update_bank_trust(101),
+        Err(Error::InvalidTrust(101))
+    ));
+}
+
+#[test]
+fn deserialize_connection_request() {
+    let data = ChainData::ConnectionRequest {
+        ip_address: "10.0.0.1".to_string(),
+        port: 80,
+        protocol: "http".to_string(),
+    };
+
+    let json = serde_json::to_string(&data).unwrap();
+    assert_eq!(
+        json,
+        r#"{"ip_address":"10.0.0.1","port":80,"protocol":"http"}"#
+    );
+
+    let message: ChainData = serde_json::from_str(&json).unwrap();
+    assert_eq!(message, data);
+    assert_eq!(message.trust(), None);
+}
+
+#[test]
+fn serialize_maintenance_requests() {
+    assert_eq!(
+        serde_json::to_string(&ChainData::start_crawl()).unwrap(),
+        r#"{"crawl":"start"}"#
+    );
+
+    let message: ChainData = serde_json::from_str(r#"{"clean":"stop"}"#).unwrap();
+    assert_eq!(message, ChainData::stop_clean());
+}
+
+#[test]
+fn deserialize_primary_validator_messages() {
+    let message: ChainData =
+        serde_json::from_str(r#"{"validator_node_identifier":"abc"}"#).unwrap();
+    assert_eq!(
+        message,
+        ChainData::UpgradeRequest {
+            validator_node_identifier: "abc".to_string()
+        }
+    );
+
+    // primary validator updates have the same shape as connection requests
+    let data = ChainData::PrimaryValidatorUpdated {
+        ip_address: "10.0.0.1".to_string(),
+        port: 80,
+        protocol: "http".to_string(),
+    };
+    let json = serde_json::to_string(&data).unwrap();
+
+    let message = ChainData::parse(&json, ChainDataKind::PrimaryValidatorUpdated).unwrap();
+    assert_eq!(message, data);
+    assert_eq!(
+        ChainData::parse(&json, ChainDataKind::ConnectionRequest)
+            .unwrap()
+            .kind(),
+        ChainDataKind::ConnectionRequest
+    );
+
+    let signed_message = Account::new().create_signed_message(&data).unwrap();
+    let parsed = SignedMessage::parse(
+        &serde_json::to_string(&signed_message).unwrap(),
+        ChainDataKind::PrimaryValidatorUpdated,
+    )
+    .unwrap();
+    assert_eq!(parsed, signed_message);
+    assert!(parsed.verify().unwrap());
+
+    assert!(matches!(
+        ChainData::parse(&json, ChainDataKind::UpgradeRequest),
+        Err(Error::UnexpectedMessage(ChainDataKind::UpgradeRequest))
+    ));
+}
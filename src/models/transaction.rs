@@ -1,8 +1,6 @@
-use crate::{
-    account::Account,
-    models::{PaginatedQueryTrait, SearchParams},
-};
+use crate::models::{PaginatedQueryTrait, SearchParams};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Enum that specifies a Node's type
 #[derive(Debug, Deserialize, Serialize, Eq, Ord, PartialEq, PartialOrd, Clone)]
@@ -17,20 +15,20 @@ pub enum NodeType {
     NONE,
 }
 
-impl NodeType {
-    /// Converts node type to string
-    pub fn to_string(&self) -> String {
+impl fmt::Display for NodeType {
+    /// Formats the node type the way the network represents it
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            NodeType::BANK => String::from("BANK"),
-            NodeType::PRIMARY_VALIDATOR => String::from("PRIMARY_VALIDATOR"),
-            NodeType::NONE => String::from("NONE"),
+            NodeType::BANK => write!(f, "BANK"),
+            NodeType::PRIMARY_VALIDATOR => write!(f, "PRIMARY_VALIDATOR"),
+            NodeType::NONE => write!(f, "NONE"),
         }
     }
 }
 
 /// Transaction Data
 #[derive(Debug, Deserialize, Serialize, Eq, Ord, PartialEq, PartialOrd, Clone)]
-pub struct Transaction<'tx> {
+pub struct Transaction {
     /// amount of coins to send
     pub amount: u64,
 
@@ -40,34 +38,30 @@ pub struct Transaction<'tx> {
 
     /// optional message to add to the transaction
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub memo: Option<&'tx str>,
+    pub memo: Option<String>,
 
     /// The recipients account number
-    pub recipient: &'tx str,
+    pub recipient: String,
 }
 
-impl Transaction<'_> {
+impl Transaction {
     /// Create a new transaction with only a recipient and an amount
-    pub fn new<'tx>(recipient: &'tx str, amount: u64) -> Transaction<'tx> {
+    pub fn new(recipient: &str, amount: u64) -> Transaction {
         Transaction {
-            amount: amount,
-            recipient: recipient,
+            amount,
+            recipient: recipient.to_string(),
             fee: None,
             memo: None,
         }
     }
 
     /// Create a new transaction with an additional parameter for a memo
-    pub fn new_with_memo<'tx>(
-        recipient: &'tx str,
-        amount: u64,
-        memo: &'tx str,
-    ) -> Transaction<'tx> {
+    pub fn new_with_memo(recipient: &str, amount: u64, memo: &str) -> Transaction {
         Transaction {
-            amount: amount,
-            recipient: recipient,
+            amount,
+            recipient: recipient.to_string(),
             fee: None,
-            memo: Some(memo),
+            memo: Some(memo.to_string()),
         }
     }
 }
@@ -97,6 +91,12 @@ impl<'a> PaginatedQueryTrait<'a> for TransactionQueryBuilder<'a> {
     }
 }
 
+impl Default for TransactionQueryBuilder<'_> {
+    fn default() -> Self {
+        TransactionQueryBuilder::new()
+    }
+}
+
 impl<'a> TransactionQueryBuilder<'a> {
     /// Initialize a new transaction query builder
     pub fn new() -> Self {
@@ -166,14 +166,17 @@ impl<'a> TransactionQueryBuilder<'a> {
     }
 }
 
-#[test]
+#[cfg(test)]
+use crate::account::Account;
 
+#[test]
 fn transaction_search_params() {
     let mut tx_query = TransactionQueryBuilder::new();
 
     let acc1 = Account::new();
     let acc_num = acc1.account_number();
-    let balance_key = acc1.signing_key();
+    let signing_key = acc1.signing_key();
+    let balance_key = signing_key.as_str();
 
     tx_query
         .limit(25)
@@ -0,0 +1,111 @@
+use std::{fmt, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+/// What a trust entry changes the trust of
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustTarget {
+    /// An account, identified by its account number
+    Account,
+
+    /// Another bank, identified by its node identifier
+    Bank,
+
+    /// A validator, identified by its node identifier
+    Validator,
+}
+
+impl fmt::Display for TrustTarget {
+    /// Formats the target the way it is written in a trust table
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrustTarget::Account => write!(f, "account"),
+            TrustTarget::Bank => write!(f, "bank"),
+            TrustTarget::Validator => write!(f, "validator"),
+        }
+    }
+}
+
+/// The trust a node should give to an account, a bank or a validator
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TrustEntry {
+    /// whether the identifier belongs to an account, a bank or a validator
+    #[serde(rename = "type")]
+    pub target: TrustTarget,
+
+    /// the account number of an account or the node identifier of a node
+    pub identifier: String,
+
+    /// the trust between 0 and [`MAX_TRUST`](crate::models::MAX_TRUST)
+    pub trust: i32,
+}
+
+/// A list of trust entries that can be applied at once with
+/// [`RegularNode::apply_trust_table`](crate::nodes::RegularNode::apply_trust_table)
+///
+/// The table is stored as a json array of entries:
+/// ```json
+/// [
+///     { "type": "account", "identifier": "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224", "trust": 20 },
+///     { "type": "bank", "identifier": "59479a31c3b91d96bb7a0b3e07f18d4bf301f1bb0bde05f8d36d9611dcbe7cbf", "trust": 75 },
+///     { "type": "validator", "identifier": "3afdf37573f1a511def0bd85553404b7091a76bcd79cdcebba1310527b167521", "trust": 100 }
+/// ]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct TrustTable {
+    /// the entries in the order they are applied
+    pub entries: Vec<TrustEntry>,
+}
+
+impl TrustTable {
+    /// Parses a trust table from json
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Reads a trust table from a json file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        TrustTable::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+/// The outcome of applying one entry of a [`TrustTable`]
+#[derive(Debug)]
+pub struct TrustUpdate {
+    /// the entry that was applied
+    pub entry: TrustEntry,
+
+    /// the trust stored by the node, `None` if the node's response did not include it, or the
+    /// reason the entry could not be applied
+    pub result: Result<Option<String>>,
+}
+
+#[test]
+fn parse_trust_table() {
+    let table = TrustTable::from_json(
+        r#"[
+            { "type": "account", "identifier": "abc", "trust": 20 },
+            { "type": "validator", "identifier": "def", "trust": 100 }
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(table.entries.len(), 2);
+    assert_eq!(
+        table.entries[1],
+        TrustEntry {
+            target: TrustTarget::Validator,
+            identifier: "def".to_string(),
+            trust: 100,
+        }
+    );
+
+    assert!(
+        TrustTable::from_json(r#"[{ "type": "wallet", "identifier": "abc", "trust": 1 }]"#)
+            .is_err()
+    );
+}
@@ -0,0 +1,71 @@
+use crate::models::{PaginatedQueryTrait, SearchParams};
+
+/// Query Builder for a get validators network request
+///
+/// # Field
+/// - **ordering**: Orders the validators according to one of their fields, e.g. `-daily_confirmation_rate`
+/// - **limit**: The max number of validators to retrieve. The limit can be set to any number between **1** and **100**
+/// - **offset**: The number of validators to skip
+///
+#[derive(Debug)]
+pub struct ValidatorQueryBuilder<'a> {
+    params: SearchParams<'a>,
+}
+
+impl<'a> PaginatedQueryTrait<'a> for ValidatorQueryBuilder<'a> {
+    /// Mutable reference to the hashmap where the data is stored
+    fn get_mut_params(&mut self) -> &mut SearchParams<'a> {
+        &mut self.params
+    }
+}
+
+impl Default for ValidatorQueryBuilder<'_> {
+    fn default() -> Self {
+        ValidatorQueryBuilder::new()
+    }
+}
+
+impl<'a> ValidatorQueryBuilder<'a> {
+    /// Initialize a new validator query builder
+    pub fn new() -> Self {
+        ValidatorQueryBuilder {
+            params: SearchParams::new(),
+        }
+    }
+
+    /// Returns a clone of the hashmap where all the search params are stored
+    pub fn get_params(&self) -> SearchParams<'a> {
+        self.params.clone()
+    }
+
+    /// Orders the validators according to one of their fields
+    pub fn ordering(&mut self, field: &'a str) -> &mut Self {
+        self.params.insert("ordering", field.to_string());
+        self
+    }
+
+    /// Removes all the data in the query builder
+    pub fn clear(&mut self) {
+        self.params.clear()
+    }
+}
+
+#[test]
+fn validator_search_params() {
+    let mut validator_query = ValidatorQueryBuilder::new();
+    validator_query
+        .limit(10)
+        .offset(20)
+        .ordering("-daily_confirmation_rate");
+
+    let params = validator_query.get_params();
+    assert_eq!(params.get("limit"), Some(&"10".to_string()));
+    assert_eq!(params.get("offset"), Some(&"20".to_string()));
+    assert_eq!(
+        params.get("ordering"),
+        Some(&"-daily_confirmation_rate".to_string())
+    );
+
+    validator_query.clear();
+    assert!(validator_query.get_params().is_empty());
+}
@@ -0,0 +1,423 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use futures_util::future::join_all;
+
+use crate::{
+    error::{Error, Result},
+    nodes::{ClientConfig, RegularNode, ServerNodeTrait},
+    responses::ConfigResponse,
+    transport::{default_transport, ReqwestTransport, Transport},
+};
+
+/// The health of a node in a [`NodePool`], measured from the requests sent to it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeHealth {
+    /// url of the node
+    pub url: String,
+
+    /// The number of requests sent to the node
+    pub requests: u64,
+
+    /// The number of requests that failed because the node could not be reached or had an internal error
+    pub errors: u64,
+
+    /// The number of requests that have failed since the last successful request
+    pub consecutive_errors: u32,
+
+    /// Moving average of the time the node takes to respond
+    pub latency: Option<Duration>,
+}
+
+impl NodeHealth {
+    /// The fraction of requests to the node that failed
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.requests as f64
+        }
+    }
+
+    /// A node is healthy if its last request did not fail
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_errors == 0
+    }
+
+    fn record_success(&mut self, elapsed: Duration) {
+        self.requests += 1;
+        self.consecutive_errors = 0;
+        self.latency = Some(match self.latency {
+            Some(latency) => (latency * 7 + elapsed * 3) / 10,
+            None => elapsed,
+        });
+    }
+
+    fn record_failure(&mut self) {
+        self.requests += 1;
+        self.errors += 1;
+        self.consecutive_errors += 1;
+    }
+
+    /// Healthy nodes come first, then the ones with the lowest error rate and latency
+    fn rank(&self) -> (u32, u64, Duration) {
+        let error_rate_per_mille = (self.error_rate() * 1000.0) as u64;
+
+        (
+            self.consecutive_errors,
+            error_rate_per_mille,
+            self.latency.unwrap_or(Duration::MAX),
+        )
+    }
+}
+
+struct PoolNodeState {
+    health: NodeHealth,
+    config: Option<ConfigResponse>,
+}
+
+struct PoolNode {
+    node: RegularNode,
+    state: Mutex<PoolNodeState>,
+}
+
+impl PoolNode {
+    fn state(&self) -> MutexGuard<'_, PoolNodeState> {
+        self.state.lock().expect("node pool lock is not poisoned")
+    }
+
+    /// Returns the last retrieved config of the node, or requests it if there is none
+    async fn config(&self) -> Result<ConfigResponse> {
+        if let Some(config) = self.state().config.clone() {
+            return Ok(config);
+        }
+
+        let config = self.node.get_config().await?;
+        self.state().config = Some(config.clone());
+
+        Ok(config)
+    }
+}
+
+/// A group of regular nodes that requests are routed through in order of their health
+///
+/// The health of each node is measured from the latency and errors of the requests sent to it.
+/// When a node can't be reached or responds with a server error, the request is retried on the
+/// next healthiest node.
+///
+/// # Example
+/// ```no_run
+///     use tnb_rs::{Account, Client, NodePool, models::Transaction};
+///
+///     #[tokio::main]
+///     async fn main() {
+///         let pool = NodePool::new(&["https://bank.keysign.app", "http://54.177.121.3"]).unwrap();
+///
+///         // probes every node and selects the healthiest one
+///         let mut client = Client::with_pool(pool).unwrap();
+///         client.update_config().await.unwrap();
+///
+///         let acc = Account::new();
+///         let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+///
+///         // the block is submitted to the next node if the healthiest one goes down
+///         client.send_transaction(&acc, &Transaction::new(recipient, 1000)).await.unwrap();
+///
+///         for health in client.pool().unwrap().health() {
+///             println!("{:?}", health);
+///         }
+///     }
+/// ```
+pub struct NodePool {
+    nodes: Vec<PoolNode>,
+}
+
+impl NodePool {
+    /// Create a pool with the urls of regular nodes
+    pub fn new(urls: &[&str]) -> Result<Self> {
+        NodePool::with_transport(urls, default_transport())
+    }
+
+    /// Create a pool whose nodes send requests with the config's settings
+    pub fn with_config(urls: &[&str], config: &ClientConfig) -> Result<Self> {
+        NodePool::with_transport(urls, Arc::new(ReqwestTransport::new(config)?))
+    }
+
+    /// Create a pool whose nodes send requests through the transport
+    pub fn with_transport(urls: &[&str], transport: Arc<dyn Transport>) -> Result<Self> {
+        if urls.is_empty() {
+            return Err(Error::EmptyNodePool);
+        }
+
+        let nodes = urls
+            .iter()
+            .map(|url| {
+                let node = RegularNode::with_transport(url, transport.clone())?;
+                let health = NodeHealth {
+                    url: node.get_base().url.clone(),
+                    ..NodeHealth::default()
+                };
+
+                Ok(PoolNode {
+                    node,
+                    state: Mutex::new(PoolNodeState {
+                        health,
+                        config: None,
+                    }),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(NodePool { nodes })
+    }
+
+    /// Requests the config of every node to measure its health
+    ///
+    /// Returns an error if none of the nodes responded
+    pub async fn probe(&self) -> Result<()> {
+        let probes = self.nodes.iter().map(|pool_node| async move {
+            let start = Instant::now();
+            let result = pool_node.node.get_config().await;
+
+            let mut state = pool_node.state();
+            match result {
+                Ok(config) => {
+                    state.health.record_success(start.elapsed());
+                    state.config = Some(config);
+                    Ok(())
+                }
+                Err(e) => {
+                    state.health.record_failure();
+                    Err(e)
+                }
+            }
+        });
+
+        let mut last_error = None;
+        for result in join_all(probes).await {
+            match result {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.expect("the pool has at least one node"))
+    }
+
+    /// The health of every node in the pool, in the order the urls were given
+    pub fn health(&self) -> Vec<NodeHealth> {
+        self.nodes
+            .iter()
+            .map(|pool_node| pool_node.state().health.clone())
+            .collect()
+    }
+
+    /// The node with the best health
+    pub fn healthiest(&self) -> &RegularNode {
+        &self.nodes[self.ranked()[0]].node
+    }
+
+    /// The last retrieved config of the node with the url
+    pub fn config(&self, url: &str) -> Option<ConfigResponse> {
+        self.nodes
+            .iter()
+            .find(|pool_node| pool_node.node.get_base().url == url)
+            .and_then(|pool_node| pool_node.state().config.clone())
+    }
+
+    /// Sends a request to the healthiest node and retries it on the other nodes if the node fails
+    ///
+    /// Only failures of the node, like connection errors and server errors, are retried.
+    /// Other errors are returned immediately.
+    ///
+    /// # Example
+    /// ```no_run
+    ///     use tnb_rs::NodePool;
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let pool = NodePool::new(&["https://bank.keysign.app", "http://54.177.121.3"]).unwrap();
+    ///
+    ///         let response = pool.request(|node| node.get_transactions(None)).await.unwrap();
+    ///     }
+    /// ```
+    pub async fn request<'a, T, F, Fut>(&'a self, f: F) -> Result<T>
+    where
+        F: Fn(&'a RegularNode) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.try_nodes(|pool_node| f(&pool_node.node)).await
+    }
+
+    /// Sends a request that depends on the node's config, like a block that pays the node's fee
+    pub(crate) async fn request_with_config<'a, T, F, Fut>(&'a self, f: F) -> Result<T>
+    where
+        F: Fn(&'a RegularNode, ConfigResponse) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let f = &f;
+        self.try_nodes(|pool_node| async move {
+            let config = pool_node.config().await?;
+            f(&pool_node.node, config).await
+        })
+        .await
+    }
+
+    async fn try_nodes<'a, T, F, Fut>(&'a self, f: F) -> Result<T>
+    where
+        F: Fn(&'a PoolNode) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut last_error = None;
+
+        for index in self.ranked() {
+            let pool_node = &self.nodes[index];
+
+            let start = Instant::now();
+            let result = f(pool_node).await;
+
+            let mut state = pool_node.state();
+            match result {
+                Err(e) if is_node_failure(&e) => {
+                    state.health.record_failure();
+                    last_error = Some(e);
+                }
+                result => {
+                    state.health.record_success(start.elapsed());
+                    return result;
+                }
+            }
+        }
+
+        Err(last_error.expect("the pool has at least one node"))
+    }
+
+    /// Indexes of the nodes from the healthiest to the least healthy
+    fn ranked(&self) -> Vec<usize> {
+        let ranks: Vec<_> = self
+            .nodes
+            .iter()
+            .map(|pool_node| pool_node.state().health.rank())
+            .collect();
+
+        let mut indexes: Vec<usize> = (0..self.nodes.len()).collect();
+        indexes.sort_by_key(|&i| ranks[i]);
+        indexes
+    }
+}
+
+/// Whether the request failed because of the node rather than the request
+pub(crate) fn is_node_failure(e: &Error) -> bool {
+    match e {
+        Error::Http(_) => true,
+        Error::HttpStatus { status, .. } => *status >= 500 || *status == 429,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+use crate::{
+    models::NodeType,
+    testing::MockNetwork,
+    transport::{HttpMethod, MemoryTransport, TransportRequest},
+};
+#[cfg(test)]
+use serde_json::json;
+
+#[cfg(test)]
+fn unreachable_url() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+#[test]
+fn empty_node_pool() {
+    assert!(matches!(NodePool::new(&[]), Err(Error::EmptyNodePool)));
+}
+
+#[tokio::test]
+async fn probe_and_rank_nodes() {
+    let network = MockNetwork::start();
+    let down = unreachable_url();
+
+    let pool = NodePool::new(&[&down, network.bank_url()]).unwrap();
+    pool.probe().await.unwrap();
+
+    let health = pool.health();
+    assert_eq!(health[0].errors, 1);
+    assert!(!health[0].is_healthy());
+    assert!(health[1].is_healthy());
+    assert!(health[1].latency.is_some());
+
+    assert_eq!(pool.healthiest().get_base().url, network.bank_url());
+    assert!(pool.config(network.bank_url()).is_some());
+    assert!(pool.config(&down).is_none());
+}
+
+#[tokio::test]
+async fn request_fails_over_to_next_node() {
+    let network = MockNetwork::start();
+    let down = unreachable_url();
+
+    let pool = NodePool::new(&[&down, network.bank_url()]).unwrap();
+    let config = pool.request(|node| node.get_config()).await.unwrap();
+
+    assert_eq!(config.node_type, NodeType::BANK);
+    assert_eq!(pool.health()[0].consecutive_errors, 1);
+    assert_eq!(pool.health()[1].requests, 1);
+
+    // the node that is down is tried last
+    pool.request(|node| node.get_config()).await.unwrap();
+    assert_eq!(pool.health()[0].requests, 1);
+}
+
+#[tokio::test]
+async fn request_returns_errors_of_the_request() {
+    let network = MockNetwork::start();
+    let pool = NodePool::new(&[network.bank_url(), network.bank_url()]).unwrap();
+
+    let result = pool
+        .request(|node| node.get_base().get_data::<ConfigResponse>("/unknown", None))
+        .await;
+
+    assert!(matches!(result, Err(Error::HttpStatus { status: 404, .. })));
+    assert_eq!(pool.health()[1].requests, 0);
+}
+
+#[tokio::test]
+async fn probe_unreachable_nodes() {
+    let pool = NodePool::new(&[&unreachable_url()]).unwrap();
+
+    assert!(matches!(pool.probe().await, Err(Error::Http(_))));
+}
+
+#[tokio::test]
+async fn pool_requests_go_through_the_transport() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.on_get("/config", 503, json!({ "detail": "Service unavailable" }));
+
+    let pool = NodePool::with_transport(&["http://10.0.0.1", "http://10.0.0.2"], transport.clone())
+        .unwrap();
+    let result = pool.request(|node| node.get_config()).await;
+
+    assert!(matches!(result, Err(Error::HttpStatus { status: 503, .. })));
+    assert_eq!(
+        transport.requests(),
+        vec![
+            TransportRequest {
+                method: HttpMethod::Get,
+                url: "http://10.0.0.1/config".to_string(),
+                body: None,
+            },
+            TransportRequest {
+                method: HttpMethod::Get,
+                url: "http://10.0.0.2/config".to_string(),
+                body: None,
+            },
+        ]
+    );
+    assert!(pool.health().iter().all(|health| health.errors == 1));
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+/// Settings for the http client the nodes use to send requests
+///
+/// A node created with a config gets its own pool of connections, which is shared with the
+/// primary validator from [`RegularNode::get_pv`](crate::nodes::RegularNode::get_pv). To share a
+/// pool between more nodes, create a [`ReqwestTransport`](crate::transport::ReqwestTransport) from
+/// the config and pass it to each node's `with_transport`.
+///
+/// # Example
+/// ```
+///     use std::time::Duration;
+///     use tnb_rs::nodes::{ClientConfig, RegularNode};
+///
+///     let mut config = ClientConfig::new();
+///     config
+///         .request_timeout(Duration::from_secs(10))
+///         .connect_timeout(Duration::from_secs(3))
+///         .max_retries(3)
+///         .retry_backoff(Duration::from_millis(250))
+///         .header("X-Api-Key", "secret");
+///
+///     let node = RegularNode::with_config("https://bank.keysign.app", &config).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    pub(crate) request_timeout: Option<Duration>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) max_retries: u32,
+    pub(crate) retry_backoff: Duration,
+    pub(crate) user_agent: String,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) proxy: Option<String>,
+    pub(crate) root_certificates: Vec<Vec<u8>>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig::new()
+    }
+}
+
+impl ClientConfig {
+    /// The default config with a 30 second request timeout, a 10 second connect timeout and no retries
+    pub fn new() -> Self {
+        ClientConfig {
+            request_timeout: Some(Duration::from_secs(30)),
+            connect_timeout: Some(Duration::from_secs(10)),
+            max_retries: 0,
+            retry_backoff: Duration::from_millis(200),
+            user_agent: format!("tnb-rs/{}", env!("CARGO_PKG_VERSION")),
+            headers: vec![],
+            proxy: None,
+            root_certificates: vec![],
+        }
+    }
+
+    /// The max time to wait for a response, or `None` to wait forever
+    pub fn request_timeout(&mut self, timeout: impl Into<Option<Duration>>) -> &mut Self {
+        self.request_timeout = timeout.into();
+        self
+    }
+
+    /// The max time to wait for a connection to the node, or `None` to wait forever
+    pub fn connect_timeout(&mut self, timeout: impl Into<Option<Duration>>) -> &mut Self {
+        self.connect_timeout = timeout.into();
+        self
+    }
+
+    /// The number of times a GET request is sent again after the node could not be reached or
+    /// responded with a server error
+    ///
+    /// Blocks and other POST and PATCH requests are never retried
+    pub fn max_retries(&mut self, retries: u32) -> &mut Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// The time to wait before the first retry. The wait is doubled after every retry
+    pub fn retry_backoff(&mut self, backoff: Duration) -> &mut Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// The `User-Agent` header sent with every request
+    pub fn user_agent(&mut self, user_agent: &str) -> &mut Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Adds a header to every request
+    pub fn header(&mut self, name: &str, value: &str) -> &mut Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sends every request through the proxy at the url
+    pub fn proxy(&mut self, proxy_url: &str) -> &mut Self {
+        self.proxy = Some(proxy_url.to_string());
+        self
+    }
+
+    /// Trusts the PEM encoded certificate in addition to the system's root certificates
+    pub fn root_certificate_pem(&mut self, pem: &[u8]) -> &mut Self {
+        self.root_certificates.push(pem.to_vec());
+        self
+    }
+}
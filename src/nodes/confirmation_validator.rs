@@ -1,6 +1,13 @@
-use crate::nodes::{
-    server_node::ServerNode,
-    traits::{ServerNodeTrait, ValidatorTrait},
+use std::sync::Arc;
+
+use crate::{
+    error::Result,
+    nodes::{
+        client_config::ClientConfig,
+        server_node::ServerNode,
+        traits::{ServerNodeTrait, ValidatorTrait},
+    },
+    transport::Transport,
 };
 
 /// Confirmation Validator
@@ -25,12 +32,26 @@ impl ConfirmationValidator {
     /// ```
     ///     use tnb_rs::{nodes::{ConfirmationValidator, ValidatorTrait, ServerNodeTrait}};
     ///
-    ///     let cv = ConfirmationValidator::new("http://54.241.48.170");
+    ///     let cv = ConfirmationValidator::new("http://54.241.48.170").unwrap();
     ///
     /// ```
-    pub fn new(url: &str) -> Self {
-        ConfirmationValidator {
-            base: ServerNode::new(url),
-        }
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(ConfirmationValidator {
+            base: ServerNode::new(url)?,
+        })
+    }
+
+    /// Create a new confirmation validator instance that sends requests with the config's settings
+    pub fn with_config(url: &str, config: &ClientConfig) -> Result<Self> {
+        Ok(ConfirmationValidator {
+            base: ServerNode::with_config(url, config)?,
+        })
+    }
+
+    /// Create a new confirmation validator instance that sends requests through the transport
+    pub fn with_transport(url: &str, transport: Arc<dyn Transport>) -> Result<Self> {
+        Ok(ConfirmationValidator {
+            base: ServerNode::with_transport(url, transport)?,
+        })
     }
 }
@@ -1,9 +1,11 @@
+mod client_config;
 mod confirmation_validator;
 mod primary_validator;
 mod regular_node;
 mod server_node;
 mod traits;
 
+pub use client_config::*;
 pub use confirmation_validator::*;
 pub use primary_validator::*;
 pub use regular_node::*;
@@ -1,6 +1,13 @@
-use crate::nodes::{
-    server_node::ServerNode,
-    traits::{ServerNodeTrait, ValidatorTrait},
+use std::sync::Arc;
+
+use crate::{
+    error::Result,
+    nodes::{
+        client_config::ClientConfig,
+        server_node::ServerNode,
+        traits::{ServerNodeTrait, ValidatorTrait},
+    },
+    transport::Transport,
 };
 
 /// Primary Validator
@@ -25,20 +32,186 @@ impl PrimaryValidator {
     /// ```
     ///     use tnb_rs::{nodes::{PrimaryValidator, ValidatorTrait, ServerNodeTrait}};
     ///
-    ///     let pv = PrimaryValidator::new("http://52.52.160.149");
+    ///     let pv = PrimaryValidator::new("http://52.52.160.149").unwrap();
     ///
     /// ```
-    pub fn new(url: &str) -> Self {
-        PrimaryValidator {
-            base: ServerNode::new(url),
-        }
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(PrimaryValidator {
+            base: ServerNode::new(url)?,
+        })
+    }
+
+    /// Create a new primary validator instance that sends requests with the config's settings
+    pub fn with_config(url: &str, config: &ClientConfig) -> Result<Self> {
+        Ok(PrimaryValidator {
+            base: ServerNode::with_config(url, config)?,
+        })
+    }
+
+    /// Create a new primary validator instance that sends requests through the transport
+    pub fn with_transport(url: &str, transport: Arc<dyn Transport>) -> Result<Self> {
+        Ok(PrimaryValidator {
+            base: ServerNode::with_transport(url, transport)?,
+        })
     }
 }
 
-#[test]
+#[cfg(test)]
+use crate::{
+    account::Account,
+    models::{
+        AccountQueryBuilder, MaintenanceCommand, NodeType, PaginatedQueryTrait,
+        ValidatorQueryBuilder,
+    },
+    responses::CleanStatus,
+    testing::MockNetwork,
+};
+
+#[tokio::test]
+async fn get_pv_config() {
+    let network = MockNetwork::start();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+
+    let config = pv.get_config().await.unwrap();
+    println!("config: {:?}", config);
+
+    assert_eq!(config.node_type, NodeType::PRIMARY_VALIDATOR);
+    assert!(config.primary_validator.is_none());
+}
+
+#[tokio::test]
+async fn get_account_balance_and_balance_lock() {
+    let network = MockNetwork::start();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+    let acc = Account::new();
+
+    assert_eq!(
+        pv.get_account_balance(acc.account_number())
+            .await
+            .unwrap()
+            .balance,
+        None
+    );
+
+    network.fund_account(acc.account_number(), 500);
+
+    assert_eq!(
+        pv.get_account_balance(acc.account_number())
+            .await
+            .unwrap()
+            .balance,
+        Some(500)
+    );
+    assert_eq!(
+        pv.get_account_balance_lock(acc.account_number())
+            .await
+            .unwrap()
+            .balance_lock,
+        Some(acc.account_number().to_string())
+    );
+}
+
+#[tokio::test]
+async fn get_pv_banks() {
+    let network = MockNetwork::start();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+
+    let response = pv.get_banks(None).await.unwrap();
+
+    assert_eq!(response.count, 1);
+    assert_eq!(response.results[0].trust, "100.00");
+}
+
+#[tokio::test]
+async fn get_pv_validators() {
+    let network = MockNetwork::start();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+
+    let mut validator_query = ValidatorQueryBuilder::new();
+    validator_query.limit(1);
+
+    let response = pv.get_validators(Some(&validator_query)).await.unwrap();
+
+    assert_eq!(response.results.len(), 1);
+    assert_eq!(response.results[0].url(), network.primary_validator_url());
+}
+
+#[tokio::test]
+async fn get_pv_accounts() {
+    let network = MockNetwork::start();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+    for amount in [30, 10, 20] {
+        network.fund_account(Account::new().account_number(), amount);
+    }
+
+    let mut account_query = AccountQueryBuilder::new();
+    account_query.ordering("-balance");
+
+    let response = pv.get_accounts(Some(&account_query)).await.unwrap();
+    let balances: Vec<_> = response.results.iter().map(|acc| acc.balance).collect();
+
+    assert_eq!(balances, vec![Some(30), Some(20), Some(10)]);
+    assert_eq!(
+        response.results[0].balance_lock.as_deref(),
+        Some(response.results[0].account_number.as_str())
+    );
+}
+
+#[tokio::test]
+async fn get_confirmation_block_by_block_id() {
+    let network = MockNetwork::start();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+    let recipient = Account::new();
+
+    let block = network.fund_account(recipient.account_number(), 50);
+    let confirmation = network.confirm_block(&block.id).unwrap();
+
+    let response = pv
+        .get_confirmation_block_by_block_id(&confirmation.block_identifier)
+        .await
+        .unwrap();
+
+    assert_eq!(response.message.block.signature, block.signature);
+    assert_eq!(response.message.updated_balances[0].balance, 50);
+
+    let message = serde_json::to_string(&response.message).unwrap();
+    assert!(
+        Account::verify_signature(&response.signature, &message, &response.node_identifier)
+            .unwrap()
+    );
+
+    assert!(matches!(
+        pv.get_confirmation_block_by_block_id(&"0".repeat(64)).await,
+        Err(crate::error::Error::HttpStatus { status: 404, .. })
+    ));
+}
+
+#[tokio::test]
+async fn start_and_stop_pv_clean() {
+    let network = MockNetwork::start();
+    let pv = PrimaryValidator::new(network.primary_validator_url()).unwrap();
+    let signer = network.primary_validator_signer();
+
+    let status = pv
+        .send_clean_command(signer, MaintenanceCommand::Start)
+        .await
+        .unwrap();
+    assert_eq!(status.clean_status, CleanStatus::Cleaning);
+    assert_eq!(status.ip_address, "127.0.0.1");
 
-fn get_pv_config() {
-    let pv = PrimaryValidator::new("http://52.52.160.149");
+    let status = pv
+        .send_clean_command(signer, MaintenanceCommand::Stop)
+        .await
+        .unwrap();
+    assert_eq!(status.clean_status, CleanStatus::NotCleaning);
+    assert_eq!(
+        pv.get_clean_status().await.unwrap().last_cleaned,
+        status.last_cleaned
+    );
 
-    println!("config: {:?}", pv.get_config().unwrap());
+    assert!(matches!(
+        pv.send_clean_command(signer, MaintenanceCommand::Stop)
+            .await,
+        Err(crate::error::Error::HttpStatus { status: 400, .. })
+    ));
 }
@@ -1,16 +1,27 @@
+use std::sync::Arc;
+
+use futures_util::stream::BoxStream;
+
 use crate::account::Account;
-use crate::nodes::server_node::ServerNode;
+use crate::nodes::{client_config::ClientConfig, server_node::ServerNode};
 use crate::{
-    models::{BlockMessage, BlockType, NodeType, PaginatedQueryTrait, TransactionQueryBuilder},
+    error::{Error, Result},
+    models::{
+        AccountQueryBuilder, BankQueryBuilder, BlockMessage, BlockQueryBuilder, BlockType,
+        ChainData, ConfirmationBlockQueryBuilder, InvalidBlockQueryBuilder, MaintenanceCommand,
+        PaginationOptions, TransactionQueryBuilder, TrustEntry, TrustTable, TrustTarget,
+        TrustUpdate, ValidatorQueryBuilder,
+    },
     nodes::{primary_validator::PrimaryValidator, traits::ServerNodeTrait},
     responses::{
-        BlockResponse, PaginatedResponse, PrimaryValidatorConfigForNode, TransactionResponse,
+        AccountResponse, BankResponse, BlockResponse, CleanResponse, ConfirmationBlockResponse,
+        CrawlResponse, InvalidBlockResponse, PaginatedResponse, PrimaryValidatorConfigForNode,
+        TransactionResponse, ValidatorResponse,
     },
+    transport::Transport,
     utils::format_node_url,
 };
 
-use reqwest::Result;
-
 /// A regular node that retrieves chain data and forwards blocks to the primary validator
 pub struct RegularNode {
     /// base server implementation
@@ -33,13 +44,31 @@ impl RegularNode {
     /// use tnb_rs::{nodes::{RegularNode, ValidatorTrait, ServerNodeTrait}};
     ///
     /// let node_url = "https://bank.keysign.app";
-    /// let node = RegularNode::new(node_url);
+    /// let node = RegularNode::new(node_url).unwrap();
     ///
     /// ```
-    pub fn new(url: &str) -> Self {
-        RegularNode {
-            base: ServerNode::new(url),
-        }
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(RegularNode {
+            base: ServerNode::new(url)?,
+        })
+    }
+
+    /// Create a new instance that sends requests with the config's settings
+    ///
+    /// The node's primary validator from [`RegularNode::get_pv`] uses the same settings
+    pub fn with_config(url: &str, config: &ClientConfig) -> Result<Self> {
+        Ok(RegularNode {
+            base: ServerNode::with_config(url, config)?,
+        })
+    }
+
+    /// Create a new instance that sends requests through the transport
+    ///
+    /// The node's primary validator from [`RegularNode::get_pv`] uses the same transport
+    pub fn with_transport(url: &str, transport: Arc<dyn Transport>) -> Result<Self> {
+        Ok(RegularNode {
+            base: ServerNode::with_transport(url, transport)?,
+        })
     }
 
     /// Get transactions on the network
@@ -52,16 +81,19 @@ impl RegularNode {
     /// ```no_run
     ///     use tnb_rs::nodes::RegularNode;
     ///
-    ///     let node = RegularNode::new("https://bank.keysign.app");
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
     ///
-    ///     let response = node.get_transactions(None).unwrap();
+    ///         let response = node.get_transactions(None).await.unwrap();
     ///
-    ///     println!("All Transactions in the network: {}", response.count);
+    ///         println!("All Transactions in the network: {}", response.count);
     ///
-    ///     let txs = response.results;
-    ///     println!("Transactions : {:?}", txs);
+    ///         let txs = response.results;
+    ///         println!("Transactions : {:?}", txs);
     ///
-    ///     assert!(txs.len() <= 50);
+    ///         assert!(txs.len() <= 50);
+    ///     }
     ///
     /// ```
     ///
@@ -74,73 +106,537 @@ impl RegularNode {
     ///         nodes::{RegularNode, ValidatorTrait, ServerNodeTrait}
     ///     };
     ///
-    ///     let node = RegularNode::new("https://bank.keysign.app");
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
     ///
-    ///     let mut tx_query = TransactionQueryBuilder::new();
+    ///         let mut tx_query = TransactionQueryBuilder::new();
     ///
-    ///     let acc_to_search_for ="1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
-    ///     tx_query
-    ///         .fee(Some(NodeType::BANK))
-    ///         .limit(20)
-    ///         .offset(0)
-    ///         .account_number(acc_to_search_for);
-    ///     
-    ///     // This tx query says the node should look for the first 20 tx
-    ///     // fees paid to a bank node from or to the
-    ///     // given account number
-    ///     
-    ///     let response = node.get_transactions(Some(&tx_query)).unwrap();
-    ///     println!("The total number of txs after being filtered by the query: {}", response.count);
+    ///         let acc_to_search_for ="1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+    ///         tx_query
+    ///             .fee(Some(NodeType::BANK))
+    ///             .limit(20)
+    ///             .offset(0)
+    ///             .account_number(acc_to_search_for);
+    ///
+    ///         // This tx query says the node should look for the first 20 tx
+    ///         // fees paid to a bank node from or to the
+    ///         // given account number
     ///
-    ///     let txs = response.results;
-    ///     assert!(txs.len() <= 20);
+    ///         let response = node.get_transactions(Some(&tx_query)).await.unwrap();
+    ///         println!("The total number of txs after being filtered by the query: {}", response.count);
+    ///
+    ///         let txs = response.results;
+    ///         assert!(txs.len() <= 20);
+    ///     }
     ///
     /// ```
     ///
-    pub fn get_transactions<'a>(
-        &'a self,
-        query: Option<&'a TransactionQueryBuilder<'a>>,
+    pub async fn get_transactions(
+        &self,
+        query: Option<&TransactionQueryBuilder<'_>>,
     ) -> Result<PaginatedResponse<TransactionResponse>> {
-        self.base.get_data(
-            "/bank_transactions",
-            match query {
-                Some(q) => Some(q.get_params()),
-                _ => None,
-            },
-        )
+        self.base
+            .get_data("/bank_transactions", query.map(|q| q.get_params()))
+            .await
+    }
+
+    /// Lazily retrieves the transactions on every page that matches the query
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    ///     use futures_util::StreamExt;
+    ///     use tnb_rs::{models::PaginationOptions, nodes::RegularNode};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///
+    ///         let options = PaginationOptions {
+    ///             page_size: Some(100),
+    ///             max_items: Some(1_000),
+    ///         };
+    ///
+    ///         let mut txs = node.transactions_stream(None, options);
+    ///
+    ///         while let Some(tx) = txs.next().await {
+    ///             let tx = tx.unwrap();
+    ///
+    ///             // stop early by breaking out of the loop
+    ///             if tx.amount > 10_000 {
+    ///                 break;
+    ///             }
+    ///         }
+    ///     }
+    ///
+    /// ```
+    ///
+    pub fn transactions_stream(
+        &self,
+        query: Option<&TransactionQueryBuilder<'_>>,
+        options: PaginationOptions,
+    ) -> BoxStream<'_, Result<TransactionResponse>> {
+        self.base
+            .paginated_stream("/bank_transactions", query.map(|q| q.get_params()), options)
+    }
+
+    /// Get the blocks stored by this node
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    ///     use tnb_rs::{models::{BlockQueryBuilder, PaginatedQueryTrait}, nodes::RegularNode};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///
+    ///         let sender = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+    ///
+    ///         let mut block_query = BlockQueryBuilder::new();
+    ///         block_query.sender(sender).ordering("-created_date").limit(10);
+    ///
+    ///         let response = node.get_blocks(Some(&block_query)).await.unwrap();
+    ///
+    ///         for block in response.results {
+    ///             println!("{} was created at {}", block.balance_key, block.created_date);
+    ///         }
+    ///     }
+    /// ```
+    pub async fn get_blocks(
+        &self,
+        query: Option<&BlockQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<BlockResponse>> {
+        self.base
+            .get_data("/blocks", query.map(|q| q.get_params()))
+            .await
+    }
+
+    /// Get the confirmations the node received from validators for its blocks
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    ///     use tnb_rs::{models::ConfirmationBlockQueryBuilder, nodes::RegularNode};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///
+    ///         let mut query = ConfirmationBlockQueryBuilder::new();
+    ///         query.block("79e6b2f0-ec5a-4b8f-9a4a-1b1b6d5b0c3e");
+    ///
+    ///         let response = node.get_confirmation_blocks(Some(&query)).await.unwrap();
+    ///         println!("The block was confirmed by {} validators", response.count);
+    ///     }
+    /// ```
+    pub async fn get_confirmation_blocks(
+        &self,
+        query: Option<&ConfirmationBlockQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<ConfirmationBlockResponse>> {
+        self.base
+            .get_data("/confirmation_blocks", query.map(|q| q.get_params()))
+            .await
+    }
+
+    /// Get the blocks that validators rejected
+    pub async fn get_invalid_blocks(
+        &self,
+        query: Option<&InvalidBlockQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<InvalidBlockResponse>> {
+        self.base
+            .get_data("/invalid_blocks", query.map(|q| q.get_params()))
+            .await
+    }
+
+    /// Get the accounts this node knows about, with the trust the node gives them
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    ///     use tnb_rs::{models::{AccountQueryBuilder, PaginatedQueryTrait}, nodes::RegularNode};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///
+    ///         let mut account_query = AccountQueryBuilder::new();
+    ///         account_query.ordering("-trust").limit(10);
+    ///
+    ///         let response = node.get_accounts(Some(&account_query)).await.unwrap();
+    ///         println!("The bank knows {} accounts", response.count);
+    ///     }
+    /// ```
+    pub async fn get_accounts(
+        &self,
+        query: Option<&AccountQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<AccountResponse>> {
+        self.base.get_accounts(query).await
+    }
+
+    /// Changes the trust this node gives to an account
+    ///
+    /// Only the node's operator can change the trust, so the request is signed with the node's
+    /// identifier key. The trust has to be between 0 and [`MAX_TRUST`](crate::models::MAX_TRUST).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    ///     use tnb_rs::{Account, nodes::RegularNode};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///
+    ///         let nid_sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
+    ///         let node_signer = Account::from_signing_key(nid_sk).unwrap();
+    ///
+    ///         let acc = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+    ///         let response = node.update_account_trust(&node_signer, acc, 42).await.unwrap();
+    ///
+    ///         println!("{} is trusted {:?}", response.account_number, response.trust);
+    ///     }
+    /// ```
+    pub async fn update_account_trust(
+        &self,
+        node_signer: &Account,
+        account_number: &str,
+        trust: i32,
+    ) -> Result<AccountResponse> {
+        let data = ChainData::update_account_trust(trust)?;
+        let signed_message = node_signer.create_signed_message(&data)?;
+
+        let endpoint = format!("/accounts/{}", account_number);
+        self.base.patch_data(&endpoint, &signed_message).await
+    }
+
+    /// Changes the trust this node gives to another bank
+    ///
+    /// The request is signed with the node's identifier key like
+    /// [`update_account_trust`](Self::update_account_trust)
+    pub async fn update_bank_trust(
+        &self,
+        node_signer: &Account,
+        node_identifier: &str,
+        trust: i32,
+    ) -> Result<BankResponse> {
+        let data = ChainData::update_bank_trust(trust)?;
+        let signed_message = node_signer.create_signed_message(&data)?;
+
+        let endpoint = format!("/banks/{}", node_identifier);
+        self.base.patch_data(&endpoint, &signed_message).await
+    }
+
+    /// Changes the trust this node gives to a validator
+    ///
+    /// The request is signed with the node's identifier key like
+    /// [`update_account_trust`](Self::update_account_trust)
+    pub async fn update_validator_trust(
+        &self,
+        node_signer: &Account,
+        node_identifier: &str,
+        trust: i32,
+    ) -> Result<ValidatorResponse> {
+        let data = ChainData::update_validator_trust(trust)?;
+        let signed_message = node_signer.create_signed_message(&data)?;
+
+        let endpoint = format!("/validators/{}", node_identifier);
+        self.base.patch_data(&endpoint, &signed_message).await
+    }
+
+    /// Applies every entry of a trust table, in order
+    ///
+    /// A failed entry doesn't stop the other entries from being applied, so the result of each
+    /// entry is returned instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    ///     use tnb_rs::{Account, models::TrustTable, nodes::RegularNode};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///
+    ///         let nid_sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
+    ///         let node_signer = Account::from_signing_key(nid_sk).unwrap();
+    ///
+    ///         let table = TrustTable::from_file("trust.json").unwrap();
+    ///
+    ///         for update in node.apply_trust_table(&node_signer, &table).await {
+    ///             match update.result {
+    ///                 Ok(Some(trust)) => println!("{}: {}", update.entry.identifier, trust),
+    ///                 Ok(None) => println!("{}: updated", update.entry.identifier),
+    ///                 Err(e) => println!("{}: {}", update.entry.identifier, e),
+    ///             }
+    ///         }
+    ///     }
+    /// ```
+    pub async fn apply_trust_table(
+        &self,
+        node_signer: &Account,
+        table: &TrustTable,
+    ) -> Vec<TrustUpdate> {
+        let mut updates = Vec::with_capacity(table.entries.len());
+
+        for entry in &table.entries {
+            let TrustEntry {
+                target,
+                identifier,
+                trust,
+            } = entry;
+
+            let result = match target {
+                TrustTarget::Account => self
+                    .update_account_trust(node_signer, identifier, *trust)
+                    .await
+                    .map(|account| account.trust),
+                TrustTarget::Bank => self
+                    .update_bank_trust(node_signer, identifier, *trust)
+                    .await
+                    .map(|bank| Some(bank.trust)),
+                TrustTarget::Validator => self
+                    .update_validator_trust(node_signer, identifier, *trust)
+                    .await
+                    .map(|validator| Some(validator.trust)),
+            };
+
+            updates.push(TrustUpdate {
+                entry: entry.clone(),
+                result,
+            });
+        }
+
+        updates
+    }
+
+    /// Get the banks this node knows about
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    ///     use tnb_rs::{models::{BankQueryBuilder, PaginatedQueryTrait}, nodes::RegularNode};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///
+    ///         let mut bank_query = BankQueryBuilder::new();
+    ///         bank_query.ordering("-trust").limit(10);
+    ///
+    ///         let response = node.get_banks(Some(&bank_query)).await.unwrap();
+    ///
+    ///         for bank in response.results {
+    ///             println!("{} is trusted {}", bank.ip_address, bank.trust);
+    ///         }
+    ///     }
+    /// ```
+    pub async fn get_banks(
+        &self,
+        query: Option<&BankQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<BankResponse>> {
+        self.base.get_banks(query).await
+    }
+
+    /// Get the validators this node knows about
+    ///
+    /// The entries can be connected to with
+    /// [`ValidatorResponse::to_confirmation_validator`] or
+    /// [`ValidatorResponse::to_primary_validator`]
+    pub async fn get_validators(
+        &self,
+        query: Option<&ValidatorQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<ValidatorResponse>> {
+        self.base.get_validators(query).await
+    }
+
+    /// Get the status of the node's crawl of the network
+    pub async fn get_crawl_status(&self) -> Result<CrawlResponse> {
+        self.base.get_crawl_status().await
+    }
+
+    /// Starts or stops the node's crawl of the network
+    ///
+    /// Only the node's operator can start a crawl, so the request is signed with the node's
+    /// identifier key. The node responds with its crawl status after the command.
+    pub async fn send_crawl_command(
+        &self,
+        node_signer: &Account,
+        command: MaintenanceCommand,
+    ) -> Result<CrawlResponse> {
+        self.base.send_crawl_command(node_signer, command).await
+    }
+
+    /// Get the status of the node's clean of its stale banks and validators
+    pub async fn get_clean_status(&self) -> Result<CleanResponse> {
+        self.base.get_clean_status().await
+    }
+
+    /// Starts or stops the node's clean of the banks and validators that are no longer online
+    ///
+    /// The request is signed with the node's identifier key like
+    /// [`send_crawl_command`](Self::send_crawl_command)
+    pub async fn send_clean_command(
+        &self,
+        node_signer: &Account,
+        command: MaintenanceCommand,
+    ) -> Result<CleanResponse> {
+        self.base.send_clean_command(node_signer, command).await
     }
 
     /// Retrieve this banks primary validator
-    pub fn get_pv(&self) -> Result<PrimaryValidator> {
-        let config = self.get_config().unwrap();
+    pub async fn get_pv(&self) -> Result<PrimaryValidator> {
+        let config = self.get_config().await?;
         let PrimaryValidatorConfigForNode {
             protocol,
             ip_address,
             port,
             ..
-        } = config.primary_validator.unwrap();
+        } = config
+            .primary_validator
+            .ok_or(Error::MissingPrimaryValidatorConfig)?;
 
         let pv_url = format_node_url(&protocol, &ip_address, port);
-        Ok(PrimaryValidator::new(&pv_url))
+        PrimaryValidator::with_transport(&pv_url, self.base.transport().clone())
+    }
+
+    /// Tells another bank that this bank switched to a new primary validator
+    ///
+    /// The address of the new primary validator is read from this bank's config and signed with
+    /// the bank's identifier key, then posted to the `/primary_validator_updated` endpoint of the
+    /// target bank. The target bank responds with an error if it can't follow the switch.
+    pub async fn send_primary_validator_updated(
+        &self,
+        node_signer: &Account,
+        target: &str,
+    ) -> Result<()> {
+        let PrimaryValidatorConfigForNode {
+            protocol,
+            ip_address,
+            port,
+            ..
+        } = self
+            .get_config()
+            .await?
+            .primary_validator
+            .ok_or(Error::MissingPrimaryValidatorConfig)?;
+
+        let data = ChainData::PrimaryValidatorUpdated {
+            ip_address,
+            port,
+            protocol,
+        };
+
+        let target = ServerNode::with_transport(target, self.base.transport().clone())?;
+        target
+            .post_data::<_, serde_json::Value>(
+                "/primary_validator_updated",
+                &node_signer.create_signed_message(&data)?,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Asks a confirmation validator to become the primary validator of this bank
+    ///
+    /// The request is signed with the bank's identifier key and posted to the `/upgrade_request`
+    /// endpoint of the validator. The validator only upgrades if this bank is the bank it trusts
+    /// most.
+    pub async fn send_upgrade_request(&self, node_signer: &Account, validator: &str) -> Result<()> {
+        let validator = ServerNode::with_transport(validator, self.base.transport().clone())?;
+        let config = validator.get_config().await?;
+
+        let data = ChainData::UpgradeRequest {
+            validator_node_identifier: config.node_identifier,
+        };
+
+        validator
+            .post_data::<_, serde_json::Value>(
+                "/upgrade_request",
+                &node_signer.create_signed_message(&data)?,
+            )
+            .await?;
+
+        Ok(())
     }
 
     /// add blocks to this banks node then broadcast it to the network
-    pub fn add_blocks<'a>(
-        &'a self,
-        block_data: &'a BlockType,
-        account: &'a Account,
+    pub async fn add_blocks(
+        &self,
+        block_data: &BlockType,
+        account: &Account,
     ) -> Result<BlockResponse> {
-        let block_message = account.create_block_message(block_data);
+        let block_message = account.create_block_message(block_data)?;
+        self.submit_signed_block(&block_message).await
+    }
+
+    /// Broadcast a block that has already been signed, e.g. by a [`BlockBuilder`](crate::models::BlockBuilder) on an offline machine
+    ///
+    /// # Example
+    /// ```no_run
+    ///     use tnb_rs::{Account, models::{BlockBuilder, Transaction}, nodes::RegularNode};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let acc = Account::new();
+    ///         let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+    ///
+    ///         let block = BlockBuilder::new(&acc)
+    ///             .balance_lock(acc.account_number())
+    ///             .bank_fee("29865762fae7d26e51f6465b3fea436d513478cfb8aa068e88a927e887cdc5fc", 1)
+    ///             .primary_validator_fee("ec8f6734272e8d9d5ea995479dd6d173424be38b313a3069d5fa62e7038a08e9", 1)
+    ///             .transaction(Transaction::new(recipient, 1000))
+    ///             .build()
+    ///             .unwrap();
+    ///
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///         let response = node.submit_signed_block(&block).await.unwrap();
+    ///
+    ///         println!("block response: {:?}", response);
+    ///     }
+    /// ```
+    pub async fn submit_signed_block(&self, block: &BlockMessage) -> Result<BlockResponse> {
         self.base
-            .post_data::<BlockMessage, BlockResponse>("/blocks", &block_message)
+            .post_data::<BlockMessage, BlockResponse>("/blocks", block)
+            .await
     }
 }
 
-#[test]
-fn get_transactions() {
-    let node = RegularNode::new("https://bank.keysign.app");
+#[cfg(test)]
+use crate::{
+    models::{BlockBuilder, NodeType, PaginatedQueryTrait, Transaction},
+    nodes::ValidatorTrait,
+    responses::{CleanStatus, ConnectionRequestResult, CrawlStatus},
+    testing::{Fixture, MockNetwork},
+};
+
+#[cfg(test)]
+use futures_util::StreamExt;
+
+#[cfg(test)]
+const FIXTURE_BANK_URL: &str = "https://bank.keysign.app";
+
+#[cfg(test)]
+fn bank_fixture() -> Fixture {
+    Fixture::load(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/bank.json"
+    ))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn get_transactions() {
+    let network = MockNetwork::start();
+    for _ in 0..60 {
+        network.fund_account(Account::new().account_number(), 10);
+    }
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
 
-    let response = node.get_transactions(None).unwrap();
+    let response = node.get_transactions(None).await.unwrap();
     let txs = response.results;
 
     println!("Total Transactions: {:?}", response.count);
@@ -149,8 +645,12 @@ fn get_transactions() {
     println!("Previous Response Link: {:?}", response.previous);
 
     println!("Transactions Received: {:?}", txs.len());
+
+    assert_eq!(response.count, 60);
+    assert_eq!(txs.len(), 50);
 }
 
+#[cfg(test)]
 fn get_offset(url: String) -> u8 {
     url.split("offset=").collect::<Vec<&str>>()[1]
         .parse::<u8>()
@@ -158,28 +658,37 @@ fn get_offset(url: String) -> u8 {
         .unwrap()
 }
 
-#[test]
-fn test_null_prev_link() {
-    let node = RegularNode::new("https://bank.keysign.app");
+#[tokio::test]
+async fn test_null_prev_link() {
+    let network = MockNetwork::start();
+    let node = RegularNode::new(network.bank_url()).unwrap();
 
-    let response = node.get_transactions(None).unwrap();
+    let response = node.get_transactions(None).await.unwrap();
 
-    assert!(response.prev().is_err());
+    assert!(matches!(
+        node.get_base().prev_page(&response).await,
+        Err(Error::MissingPageLink("Prev"))
+    ));
 }
 
-#[test]
-fn test_next_paginated_data() {
-    let node = RegularNode::new("https://bank.keysign.app");
+#[tokio::test]
+async fn test_next_paginated_data() {
+    let network = MockNetwork::start();
+    for _ in 0..120 {
+        network.fund_account(Account::new().account_number(), 10);
+    }
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
 
     let mut tx_query = TransactionQueryBuilder::new();
 
     tx_query.limit(5).offset(100);
 
-    let response = node.get_transactions(Some(&tx_query)).unwrap();
+    let response = node.get_transactions(Some(&tx_query)).await.unwrap();
     let total = response.count;
-    let len = response.results.len().clone();
+    let len = response.results.len();
 
-    let next_response = response.next().unwrap();
+    let next_response = node.get_base().next_page(&response).await.unwrap();
 
     let next_next_offset = get_offset(next_response.next.clone().unwrap());
 
@@ -189,14 +698,32 @@ fn test_next_paginated_data() {
     assert_eq!(len, next_response.results.len());
 }
 
-#[test]
-fn get_bank_fees() {
-    let node = RegularNode::new("https://bank.keysign.app");
+#[tokio::test]
+async fn get_bank_fees() {
+    let network = MockNetwork::start();
+    let sender = Account::new();
+    network.fund_account(sender.account_number(), 100);
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+    let pv_config = node.get_pv().await.unwrap().get_config().await.unwrap();
+    let node_config = node.get_config().await.unwrap();
+
+    let block = BlockBuilder::new(&sender)
+        .balance_lock(sender.account_number())
+        .bank_fee(
+            &node_config.account_number,
+            node_config.default_transaction_fee,
+        )
+        .primary_validator_fee(&pv_config.account_number, pv_config.default_transaction_fee)
+        .transaction(Transaction::new(Account::new().account_number(), 10))
+        .build()
+        .unwrap();
+    node.submit_signed_block(&block).await.unwrap();
 
     let mut tx_query = TransactionQueryBuilder::new();
     tx_query.fee(Some(NodeType::BANK));
 
-    let response = node.get_transactions(Some(&tx_query)).unwrap();
+    let response = node.get_transactions(Some(&tx_query)).await.unwrap();
     let txs = response.results;
 
     println!("Total Transactions: {:?}", response.count);
@@ -206,7 +733,491 @@ fn get_bank_fees() {
 
     println!("Transactions Received: {:?}", txs.len());
 
+    assert_eq!(txs.len(), 1);
     for tx in txs {
         assert_eq!(tx.fee, Some("BANK".to_string()));
     }
 }
+
+#[tokio::test]
+async fn submit_block_with_invalid_balance_key() {
+    let network = MockNetwork::start();
+    let sender = Account::new();
+    network.fund_account(sender.account_number(), 100);
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+
+    let block = BlockBuilder::new(&sender)
+        .balance_lock(Account::new().account_number())
+        .bank_fee(Account::new().account_number(), 1)
+        .primary_validator_fee(Account::new().account_number(), 1)
+        .build()
+        .unwrap();
+
+    assert!(matches!(
+        node.submit_signed_block(&block).await,
+        Err(Error::HttpStatus { status: 400, .. })
+    ));
+    assert_eq!(network.balance(sender.account_number()), Some(100));
+}
+
+#[tokio::test]
+async fn stream_transactions_across_pages() {
+    let network = MockNetwork::start();
+    let recipient = Account::new();
+    for _ in 0..23 {
+        network.fund_account(recipient.account_number(), 10);
+    }
+    network.fund_account(Account::new().account_number(), 10);
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+
+    let mut tx_query = TransactionQueryBuilder::new();
+    tx_query.recipient(recipient.account_number());
+
+    let options = PaginationOptions {
+        page_size: Some(5),
+        max_items: None,
+    };
+    let txs: Vec<_> = node
+        .transactions_stream(Some(&tx_query), options)
+        .collect()
+        .await;
+
+    assert_eq!(txs.len(), 23);
+    assert!(txs
+        .iter()
+        .all(|tx| tx.as_ref().unwrap().recipient == recipient.account_number()));
+
+    let options = PaginationOptions {
+        page_size: Some(5),
+        max_items: Some(7),
+    };
+    let txs: Vec<_> = node.transactions_stream(None, options).collect().await;
+    assert_eq!(txs.len(), 7);
+
+    // the stream can be dropped before the last page is retrieved
+    let txs: Vec<_> = node
+        .transactions_stream(None, PaginationOptions::default())
+        .take(3)
+        .collect()
+        .await;
+    assert_eq!(txs.len(), 3);
+}
+
+#[tokio::test]
+async fn get_blocks() {
+    let network = MockNetwork::start();
+    let sender = Account::new();
+    network.fund_account(sender.account_number(), 100);
+    for _ in 0..3 {
+        network.fund_account(Account::new().account_number(), 10);
+    }
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+    let pv_config = node.get_pv().await.unwrap().get_config().await.unwrap();
+    let node_config = node.get_config().await.unwrap();
+
+    let block = BlockBuilder::new(&sender)
+        .balance_lock(sender.account_number())
+        .bank_fee(
+            &node_config.account_number,
+            node_config.default_transaction_fee,
+        )
+        .primary_validator_fee(&pv_config.account_number, pv_config.default_transaction_fee)
+        .transaction(Transaction::new(Account::new().account_number(), 10))
+        .build()
+        .unwrap();
+    let submitted = node.submit_signed_block(&block).await.unwrap();
+
+    let response = node.get_blocks(None).await.unwrap();
+    assert_eq!(response.count, 5);
+
+    let mut block_query = BlockQueryBuilder::new();
+    block_query.sender(sender.account_number());
+
+    let response = node.get_blocks(Some(&block_query)).await.unwrap();
+    assert_eq!(response.count, 1);
+    assert_eq!(response.results[0].id, submitted.id);
+
+    block_query.clear();
+    block_query.signature(&block.signature);
+
+    let response = node.get_blocks(Some(&block_query)).await.unwrap();
+    assert_eq!(response.results[0].balance_key, sender.account_number());
+
+    block_query.clear();
+    block_query.ordering("-balance_key").limit(2);
+
+    let response = node.get_blocks(Some(&block_query)).await.unwrap();
+    assert_eq!(response.results.len(), 2);
+    assert!(response.results[0].balance_key >= response.results[1].balance_key);
+}
+
+#[tokio::test]
+async fn get_confirmation_and_invalid_blocks() {
+    let network = MockNetwork::start();
+    let confirmed = network.fund_account(Account::new().account_number(), 10);
+    let rejected = network.fund_account(Account::new().account_number(), 10);
+
+    let confirmation = network.confirm_block(&confirmed.id).unwrap();
+    network.invalidate_block(&rejected.id).unwrap();
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+
+    let mut confirmation_query = ConfirmationBlockQueryBuilder::new();
+    confirmation_query.block(&confirmed.id);
+
+    let response = node
+        .get_confirmation_blocks(Some(&confirmation_query))
+        .await
+        .unwrap();
+    assert_eq!(response.count, 1);
+    assert_eq!(
+        response.results[0].block_identifier,
+        confirmation.block_identifier
+    );
+
+    confirmation_query.clear();
+    confirmation_query.block(&rejected.id);
+    let response = node
+        .get_confirmation_blocks(Some(&confirmation_query))
+        .await
+        .unwrap();
+    assert_eq!(response.count, 0);
+
+    let response = node.get_invalid_blocks(None).await.unwrap();
+    assert_eq!(response.count, 1);
+    assert_eq!(response.results[0].block, rejected.id);
+}
+
+#[tokio::test]
+async fn get_accounts() {
+    let network = MockNetwork::start();
+    for _ in 0..4 {
+        network.fund_account(Account::new().account_number(), 10);
+    }
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+
+    let mut account_query = AccountQueryBuilder::new();
+    account_query.limit(3);
+
+    let response = node.get_accounts(Some(&account_query)).await.unwrap();
+    assert_eq!(response.count, 4);
+    assert_eq!(response.results.len(), 3);
+    assert_eq!(response.results[0].trust.as_deref(), Some("0.00"));
+    assert_eq!(response.results[0].balance, None);
+}
+
+#[tokio::test]
+async fn update_account_trust() {
+    let network = MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 10);
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+
+    let response = node
+        .update_account_trust(network.bank_signer(), acc.account_number(), 42)
+        .await
+        .unwrap();
+    assert_eq!(response.account_number, acc.account_number());
+    assert_eq!(response.trust.as_deref(), Some("42.00"));
+
+    let accounts = node.get_accounts(None).await.unwrap();
+    assert_eq!(accounts.results[0].trust.as_deref(), Some("42.00"));
+
+    assert!(matches!(
+        node.update_account_trust(network.bank_signer(), acc.account_number(), 101)
+            .await,
+        Err(Error::InvalidTrust(101))
+    ));
+
+    // only the node's identifier key can change the trust
+    assert!(matches!(
+        node.update_account_trust(&Account::new(), acc.account_number(), 50)
+            .await,
+        Err(Error::HttpStatus { status: 403, .. })
+    ));
+}
+
+#[tokio::test]
+async fn get_banks() {
+    let network = MockNetwork::start();
+    for i in 0..6 {
+        network.add_bank(BankResponse {
+            account_number: Account::new().account_number().to_string(),
+            ip_address: format!("10.0.0.{}", i),
+            node_identifier: Account::new().account_number().to_string(),
+            port: None,
+            protocol: "http".to_string(),
+            version: "v1.0".to_string(),
+            default_transaction_fee: i,
+            trust: "50.00".to_string(),
+        });
+    }
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+    let config = node.get_config().await.unwrap();
+
+    let response = node.get_banks(None).await.unwrap();
+    assert_eq!(response.count, 7);
+    assert_eq!(response.results[0].node_identifier, config.node_identifier);
+    assert_eq!(response.results[0].port, Some(config.port));
+
+    let mut bank_query = BankQueryBuilder::new();
+    bank_query.limit(5);
+
+    let response = node.get_banks(Some(&bank_query)).await.unwrap();
+    assert_eq!(response.results.len(), 5);
+
+    let next_response = node.get_base().next_page(&response).await.unwrap();
+    assert_eq!(next_response.results.len(), 2);
+    assert_eq!(next_response.results[1].ip_address, "10.0.0.5");
+}
+
+#[tokio::test]
+async fn get_validators() {
+    let network = MockNetwork::start();
+    let node = RegularNode::new(network.bank_url()).unwrap();
+
+    let response = node.get_validators(None).await.unwrap();
+    assert_eq!(response.count, 1);
+
+    let validator = &response.results[0];
+    assert_eq!(validator.url(), network.primary_validator_url());
+    assert_eq!(validator.daily_confirmation_rate, Some(1));
+
+    // the validators can be connected to from their entries
+    let transport = node.get_base().transport().clone();
+    let pv = validator.to_primary_validator(transport.clone()).unwrap();
+    let config = pv.get_config().await.unwrap();
+    assert_eq!(config.node_identifier, validator.node_identifier);
+
+    let cv = validator.to_confirmation_validator(transport).unwrap();
+    assert_eq!(cv.get_base().url, network.primary_validator_url());
+}
+
+#[tokio::test]
+async fn update_node_trust_from_table() {
+    let network = MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 10);
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+    let pv_config = node.get_pv().await.unwrap().get_config().await.unwrap();
+    let bank = node.get_banks(None).await.unwrap().results.remove(0);
+
+    let table = TrustTable::from_json(&format!(
+        r#"[
+            {{ "type": "account", "identifier": "{}", "trust": 10 }},
+            {{ "type": "bank", "identifier": "{}", "trust": 75 }},
+            {{ "type": "validator", "identifier": "{}", "trust": 100 }},
+            {{ "type": "validator", "identifier": "{}", "trust": 20 }},
+            {{ "type": "bank", "identifier": "{}", "trust": 101 }}
+        ]"#,
+        acc.account_number(),
+        bank.node_identifier,
+        pv_config.node_identifier,
+        Account::new().account_number(),
+        bank.node_identifier,
+    ))
+    .unwrap();
+
+    let updates = node.apply_trust_table(network.bank_signer(), &table).await;
+    assert_eq!(updates.len(), 5);
+    assert_eq!(
+        updates[0].result.as_ref().unwrap().as_deref(),
+        Some("10.00")
+    );
+    assert_eq!(
+        updates[1].result.as_ref().unwrap().as_deref(),
+        Some("75.00")
+    );
+    assert_eq!(
+        updates[2].result.as_ref().unwrap().as_deref(),
+        Some("100.00")
+    );
+    assert!(matches!(
+        updates[3].result,
+        Err(Error::HttpStatus { status: 404, .. })
+    ));
+    assert!(matches!(updates[4].result, Err(Error::InvalidTrust(101))));
+
+    let validators = node.get_validators(None).await.unwrap();
+    assert_eq!(validators.results[0].trust, "100.00");
+
+    // only the node's identifier key can change the trust
+    assert!(matches!(
+        node.update_bank_trust(&Account::new(), &bank.node_identifier, 50)
+            .await,
+        Err(Error::HttpStatus { status: 403, .. })
+    ));
+    let banks = node.get_banks(None).await.unwrap();
+    assert_eq!(banks.results[0].trust, "75.00");
+}
+
+#[tokio::test]
+async fn send_connection_request() {
+    let network = MockNetwork::start();
+    let new_network = MockNetwork::start();
+
+    let new_bank = RegularNode::new(new_network.bank_url()).unwrap();
+    let config = new_bank.get_config().await.unwrap();
+
+    let result = new_bank
+        .send_connection_request(new_network.bank_signer(), network.bank_url())
+        .await
+        .unwrap();
+    assert!(result.is_accepted());
+
+    let requests = network.connection_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].node_identifier, config.node_identifier);
+    assert_eq!(
+        requests[0].message,
+        ChainData::ConnectionRequest {
+            ip_address: config.ip_address,
+            port: config.port,
+            protocol: config.protocol,
+        }
+    );
+
+    let result = new_bank
+        .send_connection_request(new_network.bank_signer(), network.bank_url())
+        .await
+        .unwrap();
+    assert!(matches!(
+        result,
+        ConnectionRequestResult::Rejected { status: 400, .. }
+    ));
+
+    assert!(matches!(
+        new_bank
+            .send_connection_request(new_network.bank_signer(), "not a url")
+            .await,
+        Err(Error::InvalidUrl(_))
+    ));
+}
+
+#[tokio::test]
+async fn start_and_stop_crawl() {
+    let network = MockNetwork::start();
+    let node = RegularNode::new(network.bank_url()).unwrap();
+
+    let status = node.get_crawl_status().await.unwrap();
+    assert_eq!(status.crawl_status, CrawlStatus::NotCrawling);
+    assert_eq!(status.last_crawled, None);
+
+    let status = node
+        .send_crawl_command(network.bank_signer(), MaintenanceCommand::Start)
+        .await
+        .unwrap();
+    assert_eq!(status.crawl_status, CrawlStatus::Crawling);
+
+    assert!(matches!(
+        node.send_crawl_command(network.bank_signer(), MaintenanceCommand::Start)
+            .await,
+        Err(Error::HttpStatus { status: 400, .. })
+    ));
+
+    // only the node's identifier key can start or stop a crawl
+    assert!(matches!(
+        node.send_crawl_command(&Account::new(), MaintenanceCommand::Stop)
+            .await,
+        Err(Error::HttpStatus { status: 403, .. })
+    ));
+
+    node.send_crawl_command(network.bank_signer(), MaintenanceCommand::Stop)
+        .await
+        .unwrap();
+
+    let status = node.get_crawl_status().await.unwrap();
+    assert_eq!(status.crawl_status, CrawlStatus::NotCrawling);
+    assert!(status.last_crawled.is_some());
+
+    // the clean is a separate task
+    let status = node.get_clean_status().await.unwrap();
+    assert_eq!(status.clean_status, CleanStatus::NotCleaning);
+    assert_eq!(status.last_cleaned, None);
+}
+
+#[tokio::test]
+async fn send_primary_validator_messages() {
+    let network = MockNetwork::start();
+    let other_network = MockNetwork::start();
+
+    let node = RegularNode::new(network.bank_url()).unwrap();
+    node.send_upgrade_request(network.bank_signer(), network.primary_validator_url())
+        .await
+        .unwrap();
+
+    // only the banks the validator knows can ask it to upgrade
+    assert!(matches!(
+        node.send_upgrade_request(&Account::new(), network.primary_validator_url())
+            .await,
+        Err(Error::HttpStatus { status: 403, .. })
+    ));
+
+    // the bank can't follow a switch to a validator it doesn't know
+    network.add_bank(other_network.bank_entry());
+    let other_bank = RegularNode::new(other_network.bank_url()).unwrap();
+    assert!(matches!(
+        other_bank
+            .send_primary_validator_updated(other_network.bank_signer(), network.bank_url())
+            .await,
+        Err(Error::HttpStatus { status: 400, .. })
+    ));
+}
+
+#[tokio::test]
+async fn get_config_from_fixture() {
+    let fixture = bank_fixture();
+    let node = RegularNode::with_transport(FIXTURE_BANK_URL, fixture.transport()).unwrap();
+
+    let config = node.get_config().await.unwrap();
+
+    assert_eq!(config.node_type, NodeType::BANK);
+    assert!(config.primary_validator.is_some());
+}
+
+#[tokio::test]
+async fn get_transactions_from_fixture() {
+    let fixture = bank_fixture();
+    let node = RegularNode::with_transport(FIXTURE_BANK_URL, fixture.transport()).unwrap();
+
+    let mut tx_query = TransactionQueryBuilder::new();
+    tx_query.limit(2);
+
+    let response = node.get_transactions(Some(&tx_query)).await.unwrap();
+    assert_eq!(response.results.len(), 2);
+    assert!(response.previous.is_none());
+
+    let next_response = node.get_base().next_page(&response).await.unwrap();
+    assert_eq!(next_response.count, response.count);
+    assert_eq!(next_response.results.len(), 2);
+    assert_eq!(get_offset(next_response.next.clone().unwrap()), 4);
+
+    let prev_response = node.get_base().prev_page(&next_response).await.unwrap();
+    assert_eq!(prev_response.results[0].id, response.results[0].id);
+}
+
+#[tokio::test]
+async fn get_pv_from_fixture() {
+    let fixture = bank_fixture();
+    let node = RegularNode::with_transport(FIXTURE_BANK_URL, fixture.transport()).unwrap();
+
+    let pv = node.get_pv().await.unwrap();
+    let config = pv.get_config().await.unwrap();
+    assert_eq!(config.node_type, NodeType::PRIMARY_VALIDATOR);
+    assert!(config.primary_validator.is_none());
+
+    let recipient = "665d0698dbc8fb95afc25c3a4d9cf280d87a585b7999243ca6008fd03258975f";
+    let balance = pv.get_account_balance(recipient).await.unwrap();
+
+    // the balance changes on the live network
+    if !fixture.is_recording() {
+        assert_eq!(balance.balance, Some(5125));
+    }
+}
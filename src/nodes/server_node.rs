@@ -1,10 +1,22 @@
-use reqwest::{header::CONTENT_TYPE, Client, Result};
+use std::{collections::VecDeque, sync::Arc};
+
+use futures_util::stream::{self, BoxStream, StreamExt};
 use serde::{de, Serialize};
 use url::{Origin, Url};
 
 use crate::{
-    models::{NodeType, SearchParams},
-    responses::{ConfigResponse, PaginatedResponse, TransactionResponse},
+    account::Account,
+    error::{Error, Result},
+    models::{
+        AccountQueryBuilder, BankQueryBuilder, ChainData, MaintenanceCommand, PaginationOptions,
+        SearchParams, ValidatorQueryBuilder,
+    },
+    nodes::client_config::ClientConfig,
+    responses::{
+        AccountResponse, BankResponse, CleanResponse, ConfigResponse, CrawlResponse,
+        PaginatedResponse, ValidatorResponse,
+    },
+    transport::{default_transport, ReqwestTransport, Transport},
 };
 
 /// Base Api for a node
@@ -13,32 +25,114 @@ use crate::{
 pub struct ServerNode {
     /// url address of node's server
     pub url: String,
+
+    /// the http layer that sends the requests to the node
+    transport: Arc<dyn Transport>,
 }
 
 #[allow(dead_code)]
 impl ServerNode {
+    /// Creates the full url of an endpoint with its query params
+    fn get_url(&self, endpoint: &str, query: Option<SearchParams<'_>>) -> Result<Url> {
+        let url_endpoint = format!("{}{}", self.url, endpoint);
+
+        match query {
+            Some(query_params) => Url::parse_with_params(&url_endpoint, query_params.iter()),
+            None => Url::parse(&url_endpoint),
+        }
+        .map_err(|e| Error::InvalidUrl(e.to_string()))
+    }
+
     /// Get request to the Node's Server
-    #[tokio::main]
     pub async fn get_data<T: de::DeserializeOwned>(
         &self,
         endpoint: &str,
-        query: Option<SearchParams>,
+        query: Option<SearchParams<'_>>,
     ) -> Result<T> {
-        let url_endpoint = format!("{}{}", self.url, endpoint);
+        let url = self.get_url(endpoint, query)?;
+        self.get_json(url.as_str()).await
+    }
 
-        let url = match query {
-            Some(query_params) => {
-                Url::parse_with_params(&url_endpoint, query_params.iter()).unwrap()
-            }
-            None => Url::parse(&url_endpoint).unwrap(),
+    /// Get request to the full url of one of the node's endpoints
+    async fn get_json<T: de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        self.transport.get(url).await?.json()
+    }
+
+    /// Retrieve the next page of a paginated response
+    pub async fn next_page<T: de::DeserializeOwned>(
+        &self,
+        page: &PaginatedResponse<T>,
+    ) -> Result<PaginatedResponse<T>> {
+        match &page.next {
+            Some(url) => self.get_json(url).await,
+            None => Err(Error::MissingPageLink("Next")),
+        }
+    }
+
+    /// Retrieve the previous page of a paginated response
+    pub async fn prev_page<T: de::DeserializeOwned>(
+        &self,
+        page: &PaginatedResponse<T>,
+    ) -> Result<PaginatedResponse<T>> {
+        match &page.previous {
+            Some(url) => self.get_json(url).await,
+            None => Err(Error::MissingPageLink("Prev")),
+        }
+    }
+
+    /// Lazily retrieves every item of a paginated endpoint
+    ///
+    /// Pages are only requested when the items of the previous page have been consumed, so
+    /// dropping the stream stops the iteration. The stream ends after the last page, after
+    /// `options.max_items` items or after the first error.
+    pub fn paginated_stream<T>(
+        &self,
+        endpoint: &str,
+        query: Option<SearchParams<'_>>,
+        options: PaginationOptions,
+    ) -> BoxStream<'_, Result<T>>
+    where
+        T: de::DeserializeOwned + Send + 'static,
+    {
+        let mut params = query.unwrap_or_default();
+        if let Some(page_size) = options.page_size {
+            params.insert("limit", page_size.to_string());
+        }
+
+        let state = match self.get_url(endpoint, Some(params)) {
+            Ok(url) => PageState::new(Ok(url.to_string()), options.max_items),
+            Err(e) => PageState::new(Err(e), options.max_items),
         };
 
-        let response = reqwest::get(url).await?;
-        Ok(response.json::<T>().await?)
+        stream::unfold(state, move |mut state| async move {
+            loop {
+                if let Some(e) = state.error.take() {
+                    return Some((Err(e), state));
+                }
+
+                if state.remaining == Some(0) {
+                    return None;
+                }
+
+                if let Some(item) = state.items.pop_front() {
+                    state.remaining = state.remaining.map(|n| n - 1);
+                    return Some((Ok(item), state));
+                }
+
+                let url = state.next.take()?;
+                match self.get_json::<PaginatedResponse<T>>(&url).await {
+                    Ok(page) => {
+                        state.next = page.next;
+                        state.items = page.results.into();
+                    }
+                    Err(e) => state.error = Some(e),
+                }
+            }
+        })
+        .boxed()
     }
 
     /// Post request to the Node's Server
-    #[tokio::main]
     pub async fn post_data<D: Serialize, T: de::DeserializeOwned>(
         &self,
         endpoint: &str,
@@ -46,23 +140,13 @@ impl ServerNode {
     ) -> Result<T> {
         let url_endpoint = format!("{}{}", self.url, endpoint);
 
-        let client = Client::new();
-        let response = client
-            .post(url_endpoint)
-            .header(CONTENT_TYPE, "application/json")
-            .json(&data)
-            .body(serde_json::to_string(data).unwrap())
-            .send()
-            .await?;
-
-        let r = &response;
-        println!("\npost response: {:?}, text: ", r.status(),);
-
-        Ok(response.json::<T>().await?)
+        self.transport
+            .post(&url_endpoint, serde_json::to_string(data)?)
+            .await?
+            .json()
     }
 
     /// Patch request to the Node's Server
-    #[tokio::main]
     pub async fn patch_data<D: Serialize, T: de::DeserializeOwned>(
         &self,
         endpoint: &str,
@@ -70,44 +154,148 @@ impl ServerNode {
     ) -> Result<T> {
         let url_endpoint = format!("{}{}", self.url, endpoint);
 
-        let client = Client::new();
-        let response = client
-            .patch(url_endpoint)
-            .body(serde_json::to_string(data).unwrap())
-            .send()
-            .await?;
+        self.transport
+            .patch(&url_endpoint, serde_json::to_string(data)?)
+            .await?
+            .json()
+    }
+}
+
+/// The progress of a paginated stream
+struct PageState<T> {
+    next: Option<String>,
+    items: VecDeque<T>,
+    remaining: Option<usize>,
+    error: Option<Error>,
+}
+
+impl<T> PageState<T> {
+    fn new(first_page: Result<String>, max_items: Option<usize>) -> Self {
+        let (next, error) = match first_page {
+            Ok(url) => (Some(url), None),
+            Err(e) => (None, Some(e)),
+        };
 
-        Ok(response.json::<T>().await?)
+        PageState {
+            next,
+            items: VecDeque::new(),
+            remaining: max_items,
+            error,
+        }
     }
 }
 
 impl ServerNode {
     /// Initialize a new server node
-    pub fn new(url: &str) -> Self {
-        let parsed_url = Url::parse(url).unwrap();
+    pub fn new(url: &str) -> Result<Self> {
+        ServerNode::with_transport(url, default_transport())
+    }
 
-        let origin = parsed_url.origin();
+    /// Initialize a new server node that sends requests with the config's settings
+    pub fn with_config(url: &str, config: &ClientConfig) -> Result<Self> {
+        ServerNode::with_transport(url, Arc::new(ReqwestTransport::new(config)?))
+    }
 
-        let formatted_url = match origin {
-            Origin::Tuple(protocol, host, port) => format!("{}://{}:{}", protocol, host, port),
-            _ => String::new(),
-        };
+    /// Initialize a new server node that sends requests through the transport
+    pub fn with_transport(url: &str, transport: Arc<dyn Transport>) -> Result<Self> {
+        let parsed_url = Url::parse(url).map_err(|e| Error::InvalidUrl(e.to_string()))?;
+
+        match parsed_url.origin() {
+            Origin::Tuple(protocol, host, port) => Ok(ServerNode {
+                url: format!("{}://{}:{}", protocol, host, port),
+                transport,
+            }),
+            _ => Err(Error::InvalidUrl(format!("{} has no host", url))),
+        }
+    }
 
-        ServerNode { url: formatted_url }
+    /// The http layer that sends the requests to the node
+    pub fn transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
     }
 
     /// Get config for this node
-    pub fn get_config(&self) -> Result<ConfigResponse> {
-        let response = self.get_data::<ConfigResponse>("/config", None);
-        let response = response.unwrap();
+    pub async fn get_config(&self) -> Result<ConfigResponse> {
+        self.get_data::<ConfigResponse>("/config", None).await
+    }
 
-        Ok(response)
+    /// Get the accounts the node knows about
+    pub async fn get_accounts(
+        &self,
+        query: Option<&AccountQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<AccountResponse>> {
+        self.get_data("/accounts", query.map(|q| q.get_params()))
+            .await
+    }
+
+    /// Get the banks the node knows about
+    pub async fn get_banks(
+        &self,
+        query: Option<&BankQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<BankResponse>> {
+        self.get_data("/banks", query.map(|q| q.get_params())).await
+    }
+
+    /// Get the validators the node knows about
+    pub async fn get_validators(
+        &self,
+        query: Option<&ValidatorQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<ValidatorResponse>> {
+        self.get_data("/validators", query.map(|q| q.get_params()))
+            .await
+    }
+
+    /// Get the status of the node's crawl of the network
+    pub async fn get_crawl_status(&self) -> Result<CrawlResponse> {
+        self.get_data("/crawl", None).await
+    }
+
+    /// Starts or stops the node's crawl, signed with the node's identifier key
+    pub async fn send_crawl_command(
+        &self,
+        node_signer: &Account,
+        command: MaintenanceCommand,
+    ) -> Result<CrawlResponse> {
+        let data = ChainData::Crawl { crawl: command };
+        self.post_data("/crawl", &node_signer.create_signed_message(&data)?)
+            .await
+    }
+
+    /// Get the status of the node's clean of its stale banks and validators
+    pub async fn get_clean_status(&self) -> Result<CleanResponse> {
+        self.get_data("/clean", None).await
+    }
+
+    /// Starts or stops the node's clean, signed with the node's identifier key
+    pub async fn send_clean_command(
+        &self,
+        node_signer: &Account,
+        command: MaintenanceCommand,
+    ) -> Result<CleanResponse> {
+        let data = ChainData::Clean { clean: command };
+        self.post_data("/clean", &node_signer.create_signed_message(&data)?)
+            .await
     }
 }
 
-#[test]
-fn server_get_request() {
-    let node = ServerNode::new("https://bank.keysign.app");
+#[cfg(test)]
+use crate::{
+    models::NodeType,
+    responses::TransactionResponse,
+    testing::MockNetwork,
+    transport::{HttpMethod, MemoryTransport},
+};
+#[cfg(test)]
+use serde_json::json;
+
+#[tokio::test]
+async fn server_get_request() {
+    let network = MockNetwork::start();
+    for _ in 0..130 {
+        network.fund_account(Account::new().account_number(), 10);
+    }
+
+    let node = ServerNode::new(network.bank_url()).unwrap();
     let response = node
         .get_data::<PaginatedResponse<TransactionResponse>>(
             "/bank_transactions",
@@ -116,16 +304,18 @@ fn server_get_request() {
                 ("offset", "100".to_string()),
             ])),
         )
+        .await
         .unwrap();
 
     println!("{:?}", response);
     assert_eq!(response.results.len(), 25);
 }
 
-#[test]
-fn get_server_node_config() {
-    let node = ServerNode::new("https://bank.keysign.app");
-    let config = node.get_config().unwrap();
+#[tokio::test]
+async fn get_server_node_config() {
+    let network = MockNetwork::start();
+    let node = ServerNode::new(network.bank_url()).unwrap();
+    let config = node.get_config().await.unwrap();
 
     assert!(config.default_transaction_fee > 0);
     assert!(!config.ip_address.is_empty());
@@ -135,3 +325,92 @@ fn get_server_node_config() {
     assert_eq!(config.account_number.len(), 64);
     assert_eq!(config.node_identifier.len(), 64);
 }
+
+#[tokio::test]
+async fn get_unknown_endpoint() {
+    let network = MockNetwork::start();
+    let node = ServerNode::new(network.bank_url()).unwrap();
+
+    assert!(matches!(
+        node.get_data::<ConfigResponse>("/unknown", None).await,
+        Err(Error::HttpStatus { status: 404, .. })
+    ));
+}
+
+#[test]
+fn invalid_server_node_url() {
+    assert!(matches!(
+        ServerNode::new("bank.keysign.app"),
+        Err(Error::InvalidUrl(_))
+    ));
+    assert!(matches!(
+        ServerNode::new("data:text/plain,bank"),
+        Err(Error::InvalidUrl(_))
+    ));
+}
+
+#[tokio::test]
+async fn paginated_stream_with_invalid_endpoint() {
+    let network = MockNetwork::start();
+    let node = ServerNode::new(network.bank_url()).unwrap();
+
+    let results: Vec<Result<TransactionResponse>> = node
+        .paginated_stream("/unknown", None, PaginationOptions::default())
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 1);
+    assert!(matches!(
+        results[0],
+        Err(Error::HttpStatus { status: 404, .. })
+    ));
+}
+
+#[tokio::test]
+async fn requests_go_through_the_node_transport() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport
+        .on_get(
+            "/bank_transactions?limit=1",
+            200,
+            json!({
+                "count": 2,
+                "next": "http://10.0.0.1/bank_transactions?limit=1&offset=1",
+                "previous": null,
+                "results": [],
+            }),
+        )
+        .on_get(
+            "/bank_transactions?offset=1&limit=1",
+            200,
+            json!({ "count": 2, "next": null, "previous": null, "results": [] }),
+        )
+        .on_patch("/accounts/abc", 400, json!({ "detail": "Invalid trust" }));
+
+    let node = ServerNode::with_transport("http://10.0.0.1", transport.clone()).unwrap();
+
+    let page: PaginatedResponse<TransactionResponse> = node
+        .get_data(
+            "/bank_transactions",
+            Some(SearchParams::from([("limit", "1".to_string())])),
+        )
+        .await
+        .unwrap();
+    let next_page = node.next_page(&page).await.unwrap();
+
+    assert!(next_page.next.is_none());
+    assert!(matches!(
+        node.next_page(&next_page).await,
+        Err(Error::MissingPageLink("Next"))
+    ));
+    assert!(matches!(
+        node.patch_data::<_, serde_json::Value>("/accounts/abc", &json!({ "trust": 200 }))
+            .await,
+        Err(Error::HttpStatus { status: 400, .. })
+    ));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[2].method, HttpMethod::Patch);
+    assert_eq!(requests[2].body.as_deref(), Some(r#"{"trust":200}"#));
+}
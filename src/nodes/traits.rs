@@ -1,42 +1,195 @@
 use crate::{
+    account::Account,
+    error::{Error, Result},
+    models::{
+        AccountQueryBuilder, BankQueryBuilder, ChainData, MaintenanceCommand, ValidatorQueryBuilder,
+    },
     nodes::server_node::ServerNode,
-    responses::{AccountBalanceLockResponse, AccountBalanceResponse, ConfigResponse},
+    responses::{
+        AccountBalanceLockResponse, AccountBalanceResponse, AccountResponse, BankResponse,
+        CleanResponse, ConfigResponse, ConnectionRequestResult, CrawlResponse, PaginatedResponse,
+        ValidatorConfirmationBlockResponse, ValidatorResponse,
+    },
 };
-use reqwest::Result;
+use async_trait::async_trait;
 
 /// A trait for implementing nodes using the server node as a base layer.
+#[async_trait]
 pub trait ServerNodeTrait {
     /// Retrieves the base server so you can avoid implenting generic methods
     fn get_base(&self) -> &ServerNode;
 
     /// Get the config details of a node
-    fn get_config(&self) -> Result<ConfigResponse>
+    async fn get_config(&self) -> Result<ConfigResponse>
     where
         Self: Sized,
     {
-        self.get_base().get_config()
+        self.get_base().get_config().await
+    }
+
+    /// Asks another node to connect to this node
+    ///
+    /// The address of this node is read from its config and signed with the node's identifier
+    /// key, then posted to the `/connection_requests` endpoint of the target node. A refusal by the
+    /// target node is returned as [`ConnectionRequestResult::Rejected`] while errors reaching
+    /// either node are returned as errors.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    ///     use tnb_rs::{Account, nodes::{RegularNode, ServerNodeTrait}};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("http://10.0.0.1").unwrap();
+    ///
+    ///         let nid_sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
+    ///         let node_signer = Account::from_signing_key(nid_sk).unwrap();
+    ///
+    ///         let result = node
+    ///             .send_connection_request(&node_signer, "https://bank.keysign.app")
+    ///             .await
+    ///             .unwrap();
+    ///
+    ///         println!("connected: {}", result.is_accepted());
+    ///     }
+    /// ```
+    async fn send_connection_request(
+        &self,
+        node_signer: &Account,
+        target: &str,
+    ) -> Result<ConnectionRequestResult>
+    where
+        Self: Sized,
+    {
+        let base = self.get_base();
+        let config = base.get_config().await?;
+
+        let data = ChainData::ConnectionRequest {
+            ip_address: config.ip_address,
+            port: config.port,
+            protocol: config.protocol,
+        };
+        let signed_message = node_signer.create_signed_message(&data)?;
+
+        let target = ServerNode::with_transport(target, base.transport().clone())?;
+        let response = target
+            .transport()
+            .post(
+                &format!("{}/connection_requests", target.url),
+                serde_json::to_string(&signed_message)?,
+            )
+            .await?;
+
+        match response.status {
+            200..=299 => Ok(ConnectionRequestResult::Accepted),
+            status @ 400..=499 => Ok(ConnectionRequestResult::Rejected {
+                status,
+                reason: response.body,
+            }),
+            status => Err(Error::HttpStatus {
+                status,
+                body: response.body,
+            }),
+        }
     }
 }
 
 ///  A trait for implementing validator nodes using the server node as a base layer.
+#[async_trait]
 pub trait ValidatorTrait: ServerNodeTrait {
     /// Retrieve the number of coins in an account
-    fn get_account_balance<'a>(
-        &'a self,
-        account_number: &'a str,
-    ) -> Result<AccountBalanceResponse> {
+    async fn get_account_balance(&self, account_number: &str) -> Result<AccountBalanceResponse> {
         let endpoint = format!("/accounts/{}/balance", account_number);
         self.get_base()
             .get_data::<AccountBalanceResponse>(&endpoint, None)
+            .await
     }
 
     /// Get the balance lock of an account's next transaction
-    fn get_account_balance_lock<'a>(
-        &'a self,
-        account_number: &'a str,
+    async fn get_account_balance_lock(
+        &self,
+        account_number: &str,
     ) -> Result<AccountBalanceLockResponse> {
         let endpoint = format!("/accounts/{}/balance_lock", account_number);
         self.get_base()
             .get_data::<AccountBalanceLockResponse>(&endpoint, None)
+            .await
+    }
+
+    /// Get the confirmation block the validator created for a block
+    ///
+    /// `block_identifier` is the identifier the validator gave the block in its chain, which is
+    /// the `block_identifier` of the bank's [`ConfirmationBlockResponse`](crate::responses::ConfirmationBlockResponse)
+    async fn get_confirmation_block_by_block_id(
+        &self,
+        block_identifier: &str,
+    ) -> Result<ValidatorConfirmationBlockResponse> {
+        let endpoint = format!("/confirmation_blocks/{}/valid", block_identifier);
+        self.get_base()
+            .get_data::<ValidatorConfirmationBlockResponse>(&endpoint, None)
+            .await
+    }
+
+    /// Get the accounts this validator knows about, with their balances and balance locks
+    async fn get_accounts(
+        &self,
+        query: Option<&AccountQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<AccountResponse>> {
+        self.get_base().get_accounts(query).await
+    }
+
+    /// Get the banks this validator knows about
+    async fn get_banks(
+        &self,
+        query: Option<&BankQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<BankResponse>> {
+        self.get_base().get_banks(query).await
+    }
+
+    /// Get the validators this validator knows about
+    async fn get_validators(
+        &self,
+        query: Option<&ValidatorQueryBuilder<'_>>,
+    ) -> Result<PaginatedResponse<ValidatorResponse>> {
+        self.get_base().get_validators(query).await
+    }
+
+    /// Get the status of the node's crawl of the network
+    async fn get_crawl_status(&self) -> Result<CrawlResponse> {
+        self.get_base().get_crawl_status().await
+    }
+
+    /// Starts or stops the node's crawl of the network
+    ///
+    /// Only the node's operator can start a crawl, so the request is signed with the node's
+    /// identifier key. The node responds with its crawl status after the command.
+    async fn send_crawl_command(
+        &self,
+        node_signer: &Account,
+        command: MaintenanceCommand,
+    ) -> Result<CrawlResponse> {
+        self.get_base()
+            .send_crawl_command(node_signer, command)
+            .await
+    }
+
+    /// Get the status of the node's clean of its stale banks and validators
+    async fn get_clean_status(&self) -> Result<CleanResponse> {
+        self.get_base().get_clean_status().await
+    }
+
+    /// Starts or stops the node's clean of the banks and validators that are no longer online
+    ///
+    /// The request is signed with the node's identifier key like
+    /// [`send_crawl_command`](Self::send_crawl_command)
+    async fn send_clean_command(
+        &self,
+        node_signer: &Account,
+        command: MaintenanceCommand,
+    ) -> Result<CleanResponse> {
+        self.get_base()
+            .send_clean_command(node_signer, command)
+            .await
     }
 }
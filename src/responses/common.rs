@@ -1,9 +1,7 @@
 use crate::models::NodeType;
-use serde::{de, Deserialize};
+use serde::{Deserialize, Serialize};
 
-use anyhow::{bail, Result};
-
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 /// Block Response Data from a network node
 pub struct BlockResponse {
     /// Unique id recognised only by node that stored the data
@@ -26,7 +24,7 @@ pub struct BlockResponse {
 }
 
 /// Transaction Response from a network node usually returned as a paginated response
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TransactionResponse {
     /// Unique id recognised only by node that stored the data
     pub id: String,
@@ -47,8 +45,12 @@ pub struct TransactionResponse {
     pub memo: Option<String>,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 /// Generic Wrapper for paginated Data requested from a node
+///
+/// The next and previous pages are retrieved through the node that returned the page with
+/// `node.get_base().next_page(&page)` and `node.get_base().prev_page(&page)`, so they are sent
+/// through the node's transport
 pub struct PaginatedResponse<T> {
     /// The total number of records for the search query
     pub count: u64,
@@ -63,40 +65,8 @@ pub struct PaginatedResponse<T> {
     pub results: Vec<T>,
 }
 
-impl<T> PaginatedResponse<T>
-where
-    T: de::DeserializeOwned,
-{
-    /// Retrieve the next set of paginated data
-    /// > Returns an if you try and go out of bounds
-    pub fn next(&self) -> Result<PaginatedResponse<T>> {
-        self.get_paginated_data(&self.next, "Next Link is empty")
-    }
-
-    /// Retrieve the previous set of paginated data
-    /// > Returns an if you try and go out of bounds
-    pub fn prev(&self) -> Result<PaginatedResponse<T>> {
-        self.get_paginated_data(&self.previous, "Prev link is empty")
-    }
-
-    #[tokio::main]
-    async fn get_paginated_data(
-        &self,
-        url_option: &Option<String>,
-        empty_url_error_message: &'static str,
-    ) -> Result<PaginatedResponse<T>> {
-        match url_option {
-            Some(url) => {
-                let response = reqwest::get(url).await?;
-                Ok(response.json::<PaginatedResponse<T>>().await?)
-            }
-            None => bail!(empty_url_error_message),
-        }
-    }
-}
-
 /// The configuration details of a node's primary validator selection
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct PrimaryValidatorConfigForNode {
     /// Account Number of the Primary Validator
     pub account_number: String,
@@ -125,7 +95,7 @@ pub struct PrimaryValidatorConfigForNode {
     /// Hash
     pub root_account_file_hash: String,
 
-    ///
+    /// Identifier of the block the primary validator's chain was seeded from
     pub seed_block_identifier: String,
 
     /// Fee for the primary validator's confirmation services
@@ -136,7 +106,7 @@ pub struct PrimaryValidatorConfigForNode {
 }
 
 /// The current configuration details of a node
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ConfigResponse {
     /// - The configuration details of this node's primary validator selection
     ///
@@ -168,3 +138,195 @@ pub struct ConfigResponse {
     /// The node type
     pub node_type: NodeType,
 }
+
+/// A bank that a node knows about, returned by the `/banks` endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BankResponse {
+    /// Account Number of the bank
+    pub account_number: String,
+
+    /// Ip Address to connect to the bank
+    pub ip_address: String,
+
+    /// The unique Identifier for the bank
+    pub node_identifier: String,
+
+    /// Url Port, or `None` if the bank uses the protocol's default port
+    pub port: Option<u16>,
+
+    /// The communication protocol of the bank
+    pub protocol: String,
+
+    /// Current version of the bank
+    pub version: String,
+
+    /// Transaction fee for processing transactions through the bank
+    pub default_transaction_fee: u64,
+
+    /// Level of trust the node gives to the bank
+    pub trust: String,
+}
+
+/// An account that a node knows about, returned by the `/accounts` endpoint
+///
+/// Banks return the trust of the account and validators return its balance and balance lock,
+/// so the fields a node does not provide are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AccountResponse {
+    /// Unique id recognised only by node that stored the data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// The account number
+    pub account_number: String,
+
+    /// Level of trust the node gives to the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trust: Option<String>,
+
+    /// The number of coins stored in the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<u64>,
+
+    /// The unique id for the account's next transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance_lock: Option<String>,
+}
+
+/// A validator's confirmation of a block, returned by the `/confirmation_blocks` endpoint of a bank
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ConfirmationBlockResponse {
+    /// Unique id recognised only by node that stored the data
+    pub id: String,
+
+    /// The date and time when the confirmation was received
+    pub created_date: String,
+
+    /// The date and time when the confirmation was last modified
+    pub modified_date: String,
+
+    /// The identifier the validator gave the confirmed block in its chain
+    pub block_identifier: String,
+
+    /// The id of the confirmed block
+    pub block: String,
+
+    /// The id of the validator that confirmed the block
+    pub validator: String,
+}
+
+/// A validator's rejection of a block, returned by the `/invalid_blocks` endpoint of a bank
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InvalidBlockResponse {
+    /// Unique id recognised only by node that stored the data
+    pub id: String,
+
+    /// The date and time when the rejection was received
+    pub created_date: String,
+
+    /// The date and time when the rejection was last modified
+    pub modified_date: String,
+
+    /// The identifier the block would have had in the validator's chain
+    pub block_identifier: String,
+
+    /// The id of the rejected block
+    pub block: String,
+
+    /// The id of the confirmation validator that rejected the block
+    pub confirmation_validator: String,
+
+    /// The id of the primary validator that rejected the block
+    pub primary_validator: String,
+}
+
+/// Whether a node accepted a connection request sent with
+/// [`ServerNodeTrait::send_connection_request`](crate::nodes::ServerNodeTrait::send_connection_request)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionRequestResult {
+    /// The target node added the node to its known banks or validators
+    Accepted,
+
+    /// The target node refused the connection, e.g. because the nodes are already connected
+    Rejected {
+        /// the http status code of the response
+        status: u16,
+        /// the reason given by the target node
+        reason: String,
+    },
+}
+
+impl ConnectionRequestResult {
+    /// Whether the target node accepted the connection
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, ConnectionRequestResult::Accepted)
+    }
+}
+
+/// The state of a node's crawl of the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrawlStatus {
+    /// The node is looking for new banks and validators
+    Crawling,
+
+    /// No crawl is running
+    NotCrawling,
+
+    /// The crawl will stop after the node it is currently crawling
+    StopRequested,
+}
+
+/// The crawl status of a node, returned by the `/crawl` endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CrawlResponse {
+    /// The date and time the last crawl completed, or `None` if the node has never crawled
+    #[serde(rename = "crawl_last_completed")]
+    pub last_crawled: Option<String>,
+
+    /// Whether the node is crawling
+    pub crawl_status: CrawlStatus,
+
+    /// Ip Address of the node
+    pub ip_address: String,
+
+    /// Url Port, or `None` if the node uses the protocol's default port
+    pub port: Option<u16>,
+
+    /// The communication protocol of the node
+    pub protocol: String,
+}
+
+/// The state of a node's clean of its stale banks and validators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanStatus {
+    /// The node is removing the banks and validators that are no longer online
+    Cleaning,
+
+    /// No clean is running
+    NotCleaning,
+
+    /// The clean will stop after the node it is currently checking
+    StopRequested,
+}
+
+/// The clean status of a node, returned by the `/clean` endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CleanResponse {
+    /// The date and time the last clean completed, or `None` if the node has never cleaned
+    #[serde(rename = "clean_last_completed")]
+    pub last_cleaned: Option<String>,
+
+    /// Whether the node is cleaning
+    pub clean_status: CleanStatus,
+
+    /// Ip Address of the node
+    pub ip_address: String,
+
+    /// Url Port, or `None` if the node uses the protocol's default port
+    pub port: Option<u16>,
+
+    /// The communication protocol of the node
+    pub protocol: String,
+}
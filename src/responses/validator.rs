@@ -1,4 +1,14 @@
-use serde::Deserialize;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Result,
+    models::BlockMessage,
+    nodes::{ConfirmationValidator, PrimaryValidator},
+    transport::Transport,
+    utils::format_node_url,
+};
 
 /// Validator's response to the `/balance_lock` endpoint
 #[derive(Debug, Deserialize)]
@@ -13,3 +23,196 @@ pub struct AccountBalanceResponse {
     /// The number of coins stored in the account
     pub balance: Option<u64>,
 }
+
+/// The balance of an account after a confirmed block was applied
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UpdatedBalance {
+    /// The account number
+    pub account_number: String,
+
+    /// The number of coins stored in the account
+    pub balance: u64,
+
+    /// The unique id for the account's next transaction, only set for the sender of the block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance_lock: Option<String>,
+}
+
+/// The contents of a confirmation block signed by a validator
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ConfirmationBlockMessage {
+    /// The block that was confirmed
+    pub block: BlockMessage,
+
+    /// The identifier the validator gave the block in its chain
+    pub block_identifier: String,
+
+    /// The balances of the accounts in the block after it was applied
+    pub updated_balances: Vec<UpdatedBalance>,
+}
+
+/// Validator's response to the `/confirmation_blocks/{block_identifier}/valid` endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ValidatorConfirmationBlockResponse {
+    /// The signed contents of the confirmation block
+    pub message: ConfirmationBlockMessage,
+
+    /// The node identifier of the validator that signed the confirmation
+    pub node_identifier: String,
+
+    /// The validator's signature of the message
+    pub signature: String,
+}
+
+/// A validator that a node knows about, returned by the `/validators` endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ValidatorResponse {
+    /// Account Number of the validator
+    pub account_number: String,
+
+    /// Ip Address to connect to the validator
+    pub ip_address: String,
+
+    /// The unique Identifier for the validator
+    pub node_identifier: String,
+
+    /// Url Port, or `None` if the validator uses the protocol's default port
+    pub port: Option<u16>,
+
+    /// The communication protocol of the validator
+    pub protocol: String,
+
+    /// Current version of the validator
+    pub version: String,
+
+    /// Transaction fee for processing transactions with the validator
+    pub default_transaction_fee: u64,
+
+    /// Link to the initialisation data of the validator
+    pub root_account_file: String,
+
+    /// Hash of the root account file
+    pub root_account_file_hash: String,
+
+    /// Identifier of the block the validator's chain was seeded from
+    pub seed_block_identifier: String,
+
+    /// Fee for the validator's confirmation services
+    pub daily_confirmation_rate: Option<u64>,
+
+    /// Level of trust the node gives to the validator
+    pub trust: String,
+}
+
+impl ValidatorResponse {
+    /// The base url of the validator
+    pub fn url(&self) -> String {
+        match self.port {
+            Some(port) => format_node_url(&self.protocol, &self.ip_address, port),
+            None => format!("{}://{}", self.protocol, self.ip_address),
+        }
+    }
+
+    /// Connects to the validator as a confirmation validator that sends requests through the
+    /// transport, usually the transport of the node that listed the validator
+    ///
+    /// # Example
+    /// ```no_run
+    ///     use tnb_rs::nodes::{RegularNode, ServerNodeTrait};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let node = RegularNode::new("https://bank.keysign.app").unwrap();
+    ///         let validators = node.get_validators(None).await.unwrap();
+    ///
+    ///         for validator in validators.results {
+    ///             let cv = validator
+    ///                 .to_confirmation_validator(node.get_base().transport().clone())
+    ///                 .unwrap();
+    ///             println!("{:?}", cv.get_config().await);
+    ///         }
+    ///     }
+    /// ```
+    pub fn to_confirmation_validator(
+        &self,
+        transport: Arc<dyn Transport>,
+    ) -> Result<ConfirmationValidator> {
+        ConfirmationValidator::with_transport(&self.url(), transport)
+    }
+
+    /// Connects to the validator as a primary validator that sends requests through the transport
+    pub fn to_primary_validator(&self, transport: Arc<dyn Transport>) -> Result<PrimaryValidator> {
+        PrimaryValidator::with_transport(&self.url(), transport)
+    }
+}
+
+#[cfg(test)]
+fn validator_response(port: Option<u16>) -> ValidatorResponse {
+    ValidatorResponse {
+        account_number: "0".repeat(64),
+        ip_address: "54.183.16.194".to_string(),
+        node_identifier: "1".repeat(64),
+        port,
+        protocol: "http".to_string(),
+        version: "v1.0".to_string(),
+        default_transaction_fee: 1,
+        root_account_file: "http://54.183.16.194/media/root_account_file.json".to_string(),
+        root_account_file_hash: "2".repeat(64),
+        seed_block_identifier: String::new(),
+        daily_confirmation_rate: None,
+        trust: "0.00".to_string(),
+    }
+}
+
+#[test]
+fn validator_url() {
+    assert_eq!(validator_response(None).url(), "http://54.183.16.194");
+    assert_eq!(
+        validator_response(Some(8000)).url(),
+        "http://54.183.16.194:8000"
+    );
+}
+
+#[cfg(test)]
+use crate::{
+    nodes::ServerNodeTrait,
+    transport::{HttpMethod, MemoryTransport},
+};
+
+#[tokio::test]
+async fn connect_to_validator_through_transport() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.on_get(
+        "/config",
+        200,
+        serde_json::json!({
+            "primary_validator": null,
+            "account_number": "0".repeat(64),
+            "ip_address": "54.183.16.194",
+            "node_identifier": "1".repeat(64),
+            "port": 8000,
+            "protocol": "http",
+            "version": "v1.0",
+            "default_transaction_fee": 1,
+            "node_type": "PRIMARY_VALIDATOR",
+        }),
+    );
+
+    let validator = validator_response(Some(8000));
+
+    let pv = validator.to_primary_validator(transport.clone()).unwrap();
+    let config = pv.get_config().await.unwrap();
+    assert_eq!(config.node_identifier, validator.node_identifier);
+
+    let cv = validator
+        .to_confirmation_validator(transport.clone())
+        .unwrap();
+    cv.get_config().await.unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|request| request.method == HttpMethod::Get
+            && request.url == "http://54.183.16.194:8000/config"));
+}
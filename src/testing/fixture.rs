@@ -0,0 +1,153 @@
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+    thread,
+};
+
+use crate::{
+    error::Result,
+    transport::{default_transport, RecordingTransport, ReplayTransport, Transport},
+};
+
+/// Set this env variable to record fixtures from the live nodes instead of replaying them
+pub const RECORD_FIXTURES_ENV: &str = "TNB_RECORD_FIXTURES";
+
+/// A fixture file that tests replay their node requests from
+///
+/// When [`RECORD_FIXTURES_ENV`] is set, the requests are sent to the live nodes instead and the
+/// fixture file is written again when the fixture is dropped. Every fixture that is loaded from the
+/// same file while recording shares its recorder, so the file keeps the requests of all the tests
+/// that use it. Re-recording a fixture after the network has changed its responses makes the tests
+/// that decode them fail, so schema drift is caught without running the whole test suite against
+/// the network.
+///
+/// # Example
+/// ```no_run
+///     use tnb_rs::{nodes::{RegularNode, ServerNodeTrait}, testing::Fixture};
+///
+///     #[tokio::main]
+///     async fn main() {
+///         let fixture = Fixture::load("tests/fixtures/bank.json").unwrap();
+///         let node = RegularNode::with_transport("https://bank.keysign.app", fixture.transport()).unwrap();
+///
+///         let config = node.get_config().await.unwrap();
+///     }
+/// ```
+#[derive(Debug)]
+pub struct Fixture {
+    path: PathBuf,
+    recorder: Option<Arc<RecordingTransport>>,
+    transport: Arc<dyn Transport>,
+}
+
+impl Fixture {
+    /// Replays the fixture file, or records it if [`RECORD_FIXTURES_ENV`] is set
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        if env::var_os(RECORD_FIXTURES_ENV).is_some() {
+            return Ok(Fixture::recording(path, default_transport()));
+        }
+
+        let transport = Arc::new(ReplayTransport::from_file(&path)?);
+        Ok(Fixture {
+            path,
+            recorder: None,
+            transport,
+        })
+    }
+
+    /// Records the requests sent through the inner transport into the recorder of the path
+    fn recording(path: PathBuf, inner: Arc<dyn Transport>) -> Self {
+        let recorder = recorders()
+            .entry(path.clone())
+            .or_insert_with(|| Arc::new(RecordingTransport::new(inner)))
+            .clone();
+
+        Fixture {
+            path,
+            transport: recorder.clone(),
+            recorder: Some(recorder),
+        }
+    }
+
+    /// The transport to create the nodes of the test with
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        self.transport.clone()
+    }
+
+    /// Whether the requests are sent to the live nodes
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        if let Some(recorder) = &self.recorder {
+            // a failed test may not have sent all of its requests
+            if !thread::panicking() {
+                // the lock keeps fixtures dropped at the same time from writing an older recording
+                let _recorders = recorders();
+                recorder
+                    .save(&self.path)
+                    .expect("the fixture file can be written");
+            }
+        }
+    }
+}
+
+/// The recorders of the fixture files being recorded, by their path
+fn recorders() -> MutexGuard<'static, HashMap<PathBuf, Arc<RecordingTransport>>> {
+    static RECORDERS: OnceLock<Mutex<HashMap<PathBuf, Arc<RecordingTransport>>>> = OnceLock::new();
+
+    RECORDERS
+        .get_or_init(Mutex::default)
+        .lock()
+        .expect("fixture recorders lock is not poisoned")
+}
+
+#[cfg(test)]
+use crate::transport::MemoryTransport;
+
+#[tokio::test]
+async fn fixtures_of_the_same_file_share_a_recorder() {
+    let memory = Arc::new(MemoryTransport::new());
+    memory
+        .on_get("/config", 200, serde_json::json!({ "node_type": "BANK" }))
+        .on_get("/banks", 200, serde_json::json!([]));
+
+    let path = env::temp_dir().join(format!("tnb-fixture-{}.json", std::process::id()));
+    let url = "http://127.0.0.1";
+
+    let config_test = Fixture::recording(path.clone(), memory.clone());
+    let banks_test = Fixture::recording(path.clone(), memory);
+
+    config_test
+        .transport()
+        .get(&format!("{}/config", url))
+        .await
+        .unwrap();
+    banks_test
+        .transport()
+        .get(&format!("{}/banks", url))
+        .await
+        .unwrap();
+    drop(config_test);
+    drop(banks_test);
+
+    // the file has the requests of both tests
+    let replay = ReplayTransport::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        replay.get(&format!("{}/config", url)).await.unwrap().status,
+        200
+    );
+    assert_eq!(
+        replay.get(&format!("{}/banks", url)).await.unwrap().status,
+        200
+    );
+}
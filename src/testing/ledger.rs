@@ -0,0 +1,466 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use sodiumoxide::crypto::hash::sha256;
+
+use crate::{
+    account::Account,
+    models::{BlockMessage, BlockType, NodeType, Transaction},
+};
+
+/// The coins and the balance lock of an account in the ledger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerAccount {
+    /// the number of coins held by the account
+    pub balance: u64,
+
+    /// the balance key the account's next block has to use
+    pub balance_lock: String,
+}
+
+/// A block that has been accepted by the ledger
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerBlock {
+    /// unique id of the block
+    pub id: String,
+
+    /// the date and time when the block was created
+    pub created_date: String,
+
+    /// the date and time when the block was last modified
+    pub modified_date: String,
+
+    /// the balance key used by the sender
+    pub balance_key: String,
+
+    /// the sender's account number
+    pub sender: String,
+
+    /// the sender's signature
+    pub signature: String,
+}
+
+/// A transaction stored in one of the ledger's blocks
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerTransaction {
+    /// unique id of the transaction
+    pub id: String,
+
+    /// the block the transaction is stored in
+    pub block: LedgerBlock,
+
+    /// the number of coins sent
+    pub amount: u64,
+
+    /// the recipient's account number
+    pub recipient: String,
+
+    /// the node type the fee was paid to
+    pub fee: Option<NodeType>,
+
+    /// optional message added to the transaction
+    pub memo: Option<String>,
+}
+
+/// A validator's confirmation of a block in the ledger
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerConfirmationBlock {
+    /// unique id of the confirmation
+    pub id: String,
+
+    /// the date and time when the block was confirmed
+    pub created_date: String,
+
+    /// the date and time when the confirmation was last modified
+    pub modified_date: String,
+
+    /// the identifier the validator gave the block in its chain
+    pub block_identifier: String,
+
+    /// id of the confirmed block
+    pub block: String,
+
+    /// id of the validator that confirmed the block
+    pub validator: String,
+}
+
+/// A validator's rejection of a block in the ledger
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerInvalidBlock {
+    /// unique id of the rejection
+    pub id: String,
+
+    /// the date and time when the block was rejected
+    pub created_date: String,
+
+    /// the date and time when the rejection was last modified
+    pub modified_date: String,
+
+    /// the identifier the block would have had in the validator's chain
+    pub block_identifier: String,
+
+    /// id of the rejected block
+    pub block: String,
+
+    /// id of the confirmation validator that rejected the block
+    pub confirmation_validator: String,
+
+    /// id of the primary validator that rejected the block
+    pub primary_validator: String,
+}
+
+/// The reason a block was rejected by the ledger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The block's signature does not match the sender's account number
+    InvalidSignature,
+
+    /// The block's balance key is not the sender's balance lock
+    InvalidBalanceKey,
+
+    /// The block does not pay the expected fee to the given node type
+    MissingFee(NodeType),
+
+    /// The sender does not have enough coins for every transaction in the block
+    InsufficientBalance,
+}
+
+impl Rejection {
+    /// A readable description of the rejection
+    pub fn description(&self) -> String {
+        match self {
+            Rejection::InvalidSignature => "Invalid signature".to_string(),
+            Rejection::InvalidBalanceKey => "Invalid balance key".to_string(),
+            Rejection::MissingFee(node_type) => format!("Missing or invalid {} fee", node_type),
+            Rejection::InsufficientBalance => "Insufficient balance".to_string(),
+        }
+    }
+}
+
+/// The expected fee of a node
+#[derive(Debug, Clone)]
+pub struct NodeFee {
+    /// the node's account number
+    pub account_number: String,
+
+    /// the node's default transaction fee
+    pub amount: u64,
+}
+
+/// In-memory record of every account, block and transaction on the mock network
+#[derive(Debug)]
+pub struct Ledger {
+    treasury: Account,
+    bank_fee: NodeFee,
+    primary_validator_fee: NodeFee,
+    accounts: HashMap<String, LedgerAccount>,
+    blocks: Vec<LedgerBlock>,
+    block_messages: HashMap<String, BlockMessage>,
+    transactions: Vec<LedgerTransaction>,
+    confirmation_blocks: Vec<LedgerConfirmationBlock>,
+    invalid_blocks: Vec<LedgerInvalidBlock>,
+    is_holding: bool,
+    held: Vec<LedgerBlock>,
+}
+
+impl Ledger {
+    /// Create an empty ledger that charges the given fees on every block
+    pub fn new(bank_fee: NodeFee, primary_validator_fee: NodeFee) -> Self {
+        Ledger {
+            treasury: Account::new(),
+            bank_fee,
+            primary_validator_fee,
+            accounts: HashMap::new(),
+            blocks: Vec::new(),
+            block_messages: HashMap::new(),
+            transactions: Vec::new(),
+            confirmation_blocks: Vec::new(),
+            invalid_blocks: Vec::new(),
+            is_holding: false,
+            held: Vec::new(),
+        }
+    }
+
+    /// Returns the state of an account, or `None` if it has never received any coins
+    pub fn account(&self, account_number: &str) -> Option<&LedgerAccount> {
+        self.accounts.get(account_number)
+    }
+
+    /// Returns every account that has received coins, ordered by account number
+    pub fn accounts(&self) -> Vec<(&str, &LedgerAccount)> {
+        let mut accounts: Vec<_> = self
+            .accounts
+            .iter()
+            .map(|(account_number, account)| (account_number.as_str(), account))
+            .collect();
+        accounts.sort_by_key(|(account_number, _)| *account_number);
+        accounts
+    }
+
+    /// Returns every accepted block, oldest first
+    pub fn blocks(&self) -> &[LedgerBlock] {
+        &self.blocks
+    }
+
+    /// Returns the signed block message of an accepted block
+    pub fn block_message(&self, block_id: &str) -> Option<&BlockMessage> {
+        self.block_messages.get(block_id)
+    }
+
+    /// Returns every confirmation, oldest first
+    pub fn confirmation_blocks(&self) -> &[LedgerConfirmationBlock] {
+        &self.confirmation_blocks
+    }
+
+    /// Returns every rejection, oldest first
+    pub fn invalid_blocks(&self) -> &[LedgerInvalidBlock] {
+        &self.invalid_blocks
+    }
+
+    /// Records the validator's confirmation of an accepted block
+    ///
+    /// Returns `None` if the ledger has no block with the id
+    pub fn confirm(&mut self, block_id: &str, validator: &str) -> Option<LedgerConfirmationBlock> {
+        let block_identifier = self.block_identifier(block_id)?;
+        let now = timestamp();
+
+        let confirmation = LedgerConfirmationBlock {
+            id: random_id(),
+            created_date: now.clone(),
+            modified_date: now,
+            block_identifier,
+            block: block_id.to_string(),
+            validator: validator.to_string(),
+        };
+
+        self.confirmation_blocks.push(confirmation.clone());
+        Some(confirmation)
+    }
+
+    /// Records the validators' rejection of a block
+    ///
+    /// Returns `None` if the ledger has no block with the id
+    pub fn invalidate(
+        &mut self,
+        block_id: &str,
+        confirmation_validator: &str,
+        primary_validator: &str,
+    ) -> Option<LedgerInvalidBlock> {
+        let block_identifier = self.block_identifier(block_id)?;
+        let now = timestamp();
+
+        let invalid_block = LedgerInvalidBlock {
+            id: random_id(),
+            created_date: now.clone(),
+            modified_date: now,
+            block_identifier,
+            block: block_id.to_string(),
+            confirmation_validator: confirmation_validator.to_string(),
+            primary_validator: primary_validator.to_string(),
+        };
+
+        self.invalid_blocks.push(invalid_block.clone());
+        Some(invalid_block)
+    }
+
+    /// The hash of the block message, which the validators use to identify the block
+    fn block_identifier(&self, block_id: &str) -> Option<String> {
+        let block_message = self.block_messages.get(block_id)?;
+        let serialized = serde_json::to_string(block_message)
+            .expect("coin transfer blocks can always be serialized");
+
+        Some(hex::encode(sha256::hash(serialized.as_bytes())))
+    }
+
+    /// Returns every stored transaction, oldest first
+    pub fn transactions(&self) -> &[LedgerTransaction] {
+        &self.transactions
+    }
+
+    /// Sends coins from the treasury to an account
+    pub fn fund(&mut self, account_number: &str, amount: u64) -> LedgerBlock {
+        let tx = Transaction::new(account_number, amount);
+        let balance_key = self.treasury.account_number().to_string();
+        let block = BlockType::coin_transfer(balance_key, vec![&tx])
+            .expect("transactions without a memo are always valid");
+        let block_message = self
+            .treasury
+            .create_block_message(&block)
+            .expect("coin transfer blocks can always be serialized");
+
+        let block = self.store(&block_message);
+        self.transfer(&block);
+        block
+    }
+
+    /// Validates a signed block, then moves the coins and updates the sender's balance lock
+    pub fn apply(&mut self, block_message: &BlockMessage) -> Result<LedgerBlock, Rejection> {
+        if !block_message.verify().unwrap_or(false) {
+            return Err(Rejection::InvalidSignature);
+        }
+
+        let BlockType::CoinTransfer { balance_key, txs } = &block_message.message;
+
+        let sender = self
+            .accounts
+            .get(&block_message.account_number)
+            .ok_or(Rejection::InvalidBalanceKey)?;
+
+        if &sender.balance_lock != balance_key {
+            return Err(Rejection::InvalidBalanceKey);
+        }
+
+        for (node_type, fee) in [
+            (NodeType::BANK, &self.bank_fee),
+            (NodeType::PRIMARY_VALIDATOR, &self.primary_validator_fee),
+        ] {
+            let is_paid = txs.iter().any(|tx| {
+                tx.fee.as_ref() == Some(&node_type)
+                    && tx.recipient == fee.account_number
+                    && tx.amount == fee.amount
+            });
+
+            if !is_paid {
+                return Err(Rejection::MissingFee(node_type));
+            }
+        }
+
+        let total = txs
+            .iter()
+            .try_fold(0u64, |total, tx| total.checked_add(tx.amount))
+            .ok_or(Rejection::InsufficientBalance)?;
+
+        if total > sender.balance {
+            return Err(Rejection::InsufficientBalance);
+        }
+
+        let block = self.store(block_message);
+        if self.is_holding {
+            self.held.push(block.clone());
+        } else {
+            self.transfer(&block);
+        }
+
+        Ok(block)
+    }
+
+    /// Accepts new blocks without moving their coins until [`release`](Ledger::release) is called
+    ///
+    /// This simulates a primary validator that has not processed the blocks yet, so the balances
+    /// and balance locks don't change.
+    pub fn hold(&mut self) {
+        self.is_holding = true;
+    }
+
+    /// Moves the coins of every held block and stops holding new blocks
+    pub fn release(&mut self) {
+        self.is_holding = false;
+
+        for block in std::mem::take(&mut self.held) {
+            self.transfer(&block);
+        }
+    }
+
+    fn store(&mut self, block_message: &BlockMessage) -> LedgerBlock {
+        let BlockType::CoinTransfer { balance_key, .. } = &block_message.message;
+        let now = timestamp();
+
+        let block = LedgerBlock {
+            id: random_id(),
+            created_date: now.clone(),
+            modified_date: now,
+            balance_key: balance_key.clone(),
+            sender: block_message.account_number.clone(),
+            signature: block_message.signature.clone(),
+        };
+
+        self.block_messages
+            .insert(block.id.clone(), block_message.clone());
+        self.blocks.push(block.clone());
+        block
+    }
+
+    /// Moves the coins of a stored block and updates the sender's balance lock
+    fn transfer(&mut self, block: &LedgerBlock) {
+        let block_message = self.block_messages[&block.id].clone();
+        let BlockType::CoinTransfer { txs, .. } = &block_message.message;
+
+        if let Some(sender) = self.accounts.get_mut(&block_message.account_number) {
+            let total: u64 = txs.iter().map(|tx| tx.amount).sum();
+            let serialized_message = serde_json::to_string(&block_message.message)
+                .expect("coin transfer blocks can always be serialized");
+
+            sender.balance -= total;
+            sender.balance_lock = hex::encode(sha256::hash(serialized_message.as_bytes()));
+        }
+
+        for tx in txs {
+            self.accounts
+                .entry(tx.recipient.clone())
+                .or_insert_with(|| LedgerAccount {
+                    balance: 0,
+                    balance_lock: tx.recipient.clone(),
+                })
+                .balance += tx.amount;
+
+            self.transactions.push(LedgerTransaction {
+                id: random_id(),
+                block: block.clone(),
+                amount: tx.amount,
+                recipient: tx.recipient.clone(),
+                fee: tx.fee.clone(),
+                memo: tx.memo.clone(),
+            });
+        }
+    }
+}
+
+/// Creates a random id in the uuid format used by the nodes
+fn random_id() -> String {
+    let bytes: [u8; 16] = rand::random();
+    let id = hex::encode(bytes);
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &id[0..8],
+        &id[8..12],
+        &id[12..16],
+        &id[16..20],
+        &id[20..32]
+    )
+}
+
+/// Formats the current time the way the nodes format their dates
+pub(crate) fn timestamp() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("the system clock is set after 1970");
+
+    let secs = now.as_secs();
+    let (days, secs_of_day) = ((secs / 86_400) as i64, secs % 86_400);
+
+    // civil date from days since 1970-01-01
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+        now.subsec_micros()
+    )
+}
@@ -0,0 +1,373 @@
+//! An in-process bank and primary validator for testing code that talks to the network
+//!
+//! Both nodes share an in-memory [`Ledger`], so blocks sent to the bank update the balances and
+//! balance locks returned by the primary validator.
+//!
+//! [`Ledger`]: crate::testing::Ledger
+//!
+//! Tests that need the responses of the live network can replay them from a [`Fixture`] file.
+//!
+//! [`Fixture`]: crate::testing::Fixture
+//!
+//! # Example
+//! ```
+//!     use tnb_rs::{testing::MockNetwork, Account, Wallet, models::Transaction};
+//!
+//!     #[tokio::main]
+//!     async fn main() {
+//!         let network = MockNetwork::start();
+//!
+//!         let acc = Account::new();
+//!         network.fund_account(acc.account_number(), 1_000);
+//!
+//!         let mut wallet = Wallet::new(&acc, network.bank_url()).unwrap();
+//!         wallet.init().await.unwrap();
+//!
+//!         let recipient = Account::new();
+//!         let tx = Transaction::new(recipient.account_number(), 100);
+//!         wallet.send_transaction(&tx).await.unwrap();
+//!
+//!         assert_eq!(network.balance(recipient.account_number()), Some(100));
+//!     }
+//! ```
+
+mod fixture;
+mod ledger;
+mod server;
+
+pub use fixture::*;
+pub use ledger::*;
+
+use std::{
+    net::TcpListener,
+    sync::{Arc, Mutex, MutexGuard},
+    thread::{self, JoinHandle},
+};
+
+use serde_json::json;
+use tokio::sync::oneshot;
+
+use crate::{
+    account::Account,
+    models::{NodeType, SignedMessage},
+    responses::{BankResponse, ValidatorResponse},
+    testing::server::MockNode,
+};
+
+/// The transaction fee charged by the mock bank
+pub const MOCK_BANK_FEE: u64 = 1;
+
+/// The transaction fee charged by the mock primary validator
+pub const MOCK_PRIMARY_VALIDATOR_FEE: u64 = 2;
+
+/// A bank and its primary validator running on local ports
+///
+/// The servers run on a background thread, so the network can be used from both async and
+/// blocking tests. They are shut down when the network is dropped.
+pub struct MockNetwork {
+    bank: Arc<MockNode>,
+    primary_validator: Arc<MockNode>,
+    ledger: Arc<Mutex<Ledger>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockNetwork {
+    /// Starts a bank and a primary validator with an empty ledger
+    pub fn start() -> Self {
+        let bank_listener = bind();
+        let pv_listener = bind();
+
+        let bank_url = local_url(&bank_listener);
+        let pv_url = local_url(&pv_listener);
+
+        let bank_account = Account::new();
+        let pv_account = Account::new();
+
+        let ledger = Arc::new(Mutex::new(Ledger::new(
+            NodeFee {
+                account_number: bank_account.account_number().to_string(),
+                amount: MOCK_BANK_FEE,
+            },
+            NodeFee {
+                account_number: pv_account.account_number().to_string(),
+                amount: MOCK_PRIMARY_VALIDATOR_FEE,
+            },
+        )));
+
+        let pv_port = port(&pv_listener);
+        let pv_node_identifier = Account::new();
+        let bank_node_identifier = Account::new();
+
+        let banks = Arc::new(Mutex::new(vec![BankResponse {
+            account_number: bank_account.account_number().to_string(),
+            ip_address: "127.0.0.1".to_string(),
+            node_identifier: bank_node_identifier.account_number().to_string(),
+            port: Some(port(&bank_listener)),
+            protocol: "http".to_string(),
+            version: "v1.0".to_string(),
+            default_transaction_fee: MOCK_BANK_FEE,
+            trust: "100.00".to_string(),
+        }]));
+
+        let validators = Arc::new(Mutex::new(vec![ValidatorResponse {
+            account_number: pv_account.account_number().to_string(),
+            ip_address: "127.0.0.1".to_string(),
+            node_identifier: pv_node_identifier.account_number().to_string(),
+            port: Some(pv_port),
+            protocol: "http".to_string(),
+            version: "v1.0".to_string(),
+            default_transaction_fee: MOCK_PRIMARY_VALIDATOR_FEE,
+            root_account_file: format!("{}/media/root_account_file.json", pv_url),
+            root_account_file_hash: "0".repeat(64),
+            seed_block_identifier: String::new(),
+            daily_confirmation_rate: Some(1),
+            trust: "100.00".to_string(),
+        }]));
+
+        let bank = Arc::new(MockNode {
+            config: Mutex::new(json!({
+                "primary_validator": {
+                    "account_number": pv_account.account_number(),
+                    "ip_address": "127.0.0.1",
+                    "node_identifier": pv_node_identifier.account_number(),
+                    "port": pv_port,
+                    "protocol": "http",
+                    "version": "v1.0",
+                    "default_transaction_fee": MOCK_PRIMARY_VALIDATOR_FEE,
+                    "root_account_file": format!("{}/media/root_account_file.json", pv_url),
+                    "root_account_file_hash": "0".repeat(64),
+                    "seed_block_identifier": "",
+                    "daily_confirmation_rate": 1,
+                    "trust": "100.00",
+                },
+                "account_number": bank_account.account_number(),
+                "ip_address": "127.0.0.1",
+                "node_identifier": bank_node_identifier.account_number(),
+                "port": port(&bank_listener),
+                "protocol": "http",
+                "version": "v1.0",
+                "default_transaction_fee": MOCK_BANK_FEE,
+                "node_type": NodeType::BANK,
+            })),
+            identity: bank_node_identifier,
+            url: bank_url,
+            node_type: NodeType::BANK,
+            ledger: ledger.clone(),
+            account_trust: Mutex::default(),
+            banks: banks.clone(),
+            validators: validators.clone(),
+            connection_requests: Mutex::default(),
+            maintenance: Mutex::default(),
+        });
+
+        let primary_validator = Arc::new(MockNode {
+            config: Mutex::new(json!({
+                "primary_validator": null,
+                "account_number": pv_account.account_number(),
+                "ip_address": "127.0.0.1",
+                "node_identifier": pv_node_identifier.account_number(),
+                "port": pv_port,
+                "protocol": "http",
+                "version": "v1.0",
+                "default_transaction_fee": MOCK_PRIMARY_VALIDATOR_FEE,
+                "node_type": NodeType::PRIMARY_VALIDATOR,
+            })),
+            identity: pv_node_identifier,
+            url: pv_url,
+            node_type: NodeType::PRIMARY_VALIDATOR,
+            ledger: ledger.clone(),
+            account_trust: Mutex::default(),
+            banks,
+            validators,
+            connection_requests: Mutex::default(),
+            maintenance: Mutex::default(),
+        });
+
+        let (shutdown, shutdown_signal) = oneshot::channel();
+        let (bank_node, pv_node) = (bank.clone(), primary_validator.clone());
+
+        let handle = thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("failed to start the mock network runtime");
+
+            runtime.block_on(async move {
+                tokio::select! {
+                    _ = server::serve(bank_listener, bank_node) => {},
+                    _ = server::serve(pv_listener, pv_node) => {},
+                    _ = shutdown_signal => {},
+                }
+            });
+        });
+
+        MockNetwork {
+            bank,
+            primary_validator,
+            ledger,
+            shutdown: Some(shutdown),
+            handle: Some(handle),
+        }
+    }
+
+    /// The url of the mock bank
+    pub fn bank_url(&self) -> &str {
+        &self.bank.url
+    }
+
+    /// The url of the mock primary validator
+    pub fn primary_validator_url(&self) -> &str {
+        &self.primary_validator.url
+    }
+
+    /// The node identifier key of the mock bank, which signs the bank's operator requests
+    pub fn bank_signer(&self) -> &Account {
+        &self.bank.identity
+    }
+
+    /// The node identifier key of the mock primary validator
+    pub fn primary_validator_signer(&self) -> &Account {
+        &self.primary_validator.identity
+    }
+
+    /// Sends coins to an account from outside the network
+    pub fn fund_account(&self, account_number: &str, amount: u64) -> LedgerBlock {
+        self.ledger().fund(account_number, amount)
+    }
+
+    /// The number of coins held by an account
+    pub fn balance(&self, account_number: &str) -> Option<u64> {
+        self.ledger().account(account_number).map(|acc| acc.balance)
+    }
+
+    /// The balance lock of an account's next block
+    pub fn balance_lock(&self, account_number: &str) -> Option<String> {
+        self.ledger()
+            .account(account_number)
+            .map(|acc| acc.balance_lock.clone())
+    }
+
+    /// Accepts new blocks without moving their coins until [`release_blocks`](MockNetwork::release_blocks) is called
+    ///
+    /// Balances and balance locks stay the same, as if the primary validator had not processed
+    /// the blocks yet
+    pub fn hold_blocks(&self) {
+        self.ledger().hold()
+    }
+
+    /// Moves the coins of every held block and stops holding new blocks
+    pub fn release_blocks(&self) {
+        self.ledger().release()
+    }
+
+    /// Confirms an accepted block on behalf of the primary validator
+    ///
+    /// Blocks sent to the bank are pending until they are confirmed or rejected. Returns `None` if
+    /// the ledger has no block with the id.
+    pub fn confirm_block(&self, block_id: &str) -> Option<LedgerConfirmationBlock> {
+        let validator = self.primary_validator.identity.account_number();
+        self.ledger().confirm(block_id, validator)
+    }
+
+    /// Rejects a block on behalf of the primary validator
+    ///
+    /// Returns `None` if the ledger has no block with the id
+    pub fn invalidate_block(&self, block_id: &str) -> Option<LedgerInvalidBlock> {
+        let validator = self.primary_validator.identity.account_number();
+        self.ledger().invalidate(block_id, validator, validator)
+    }
+
+    /// Adds a bank to the banks known by the nodes
+    ///
+    /// The mock bank is the only bank the nodes know about when the network is started
+    pub fn add_bank(&self, bank: BankResponse) {
+        self.bank
+            .banks
+            .lock()
+            .expect("banks lock is not poisoned")
+            .push(bank);
+    }
+
+    /// The connection requests accepted by the bank, in the order they were received
+    pub fn connection_requests(&self) -> Vec<SignedMessage> {
+        self.bank
+            .connection_requests
+            .lock()
+            .expect("connection requests lock is not poisoned")
+            .clone()
+    }
+
+    /// The entry of the mock bank in the banks known by the nodes, used to add the bank to another
+    /// network with [`add_bank`](Self::add_bank)
+    pub fn bank_entry(&self) -> BankResponse {
+        let node_identifier = self.bank.identity.account_number();
+
+        self.bank
+            .banks
+            .lock()
+            .expect("banks lock is not poisoned")
+            .iter()
+            .find(|bank| bank.node_identifier == node_identifier)
+            .cloned()
+            .expect("the mock bank knows itself")
+    }
+
+    /// The entry of the mock primary validator in the validators known by the nodes, used to add
+    /// the validator to another network with [`add_validator`](Self::add_validator)
+    pub fn primary_validator_entry(&self) -> ValidatorResponse {
+        let node_identifier = self.primary_validator.identity.account_number();
+
+        self.bank
+            .validators
+            .lock()
+            .expect("validators lock is not poisoned")
+            .iter()
+            .find(|validator| validator.node_identifier == node_identifier)
+            .cloned()
+            .expect("the mock network knows its primary validator")
+    }
+
+    /// Adds a validator to the validators known by the nodes
+    ///
+    /// The mock primary validator is the only validator the nodes know about when the network is started
+    pub fn add_validator(&self, validator: ValidatorResponse) {
+        self.bank
+            .validators
+            .lock()
+            .expect("validators lock is not poisoned")
+            .push(validator);
+    }
+
+    /// Locks the ledger shared by the nodes so it can be inspected
+    pub fn ledger(&self) -> MutexGuard<'_, Ledger> {
+        self.ledger.lock().expect("ledger lock is not poisoned")
+    }
+}
+
+impl Drop for MockNetwork {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn bind() -> TcpListener {
+    TcpListener::bind("127.0.0.1:0").expect("failed to bind a local port for the mock network")
+}
+
+fn port(listener: &TcpListener) -> u16 {
+    listener
+        .local_addr()
+        .expect("the listener is bound to a local address")
+        .port()
+}
+
+fn local_url(listener: &TcpListener) -> String {
+    format!("http://127.0.0.1:{}", port(listener))
+}
@@ -0,0 +1,824 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    convert::Infallible,
+    future::Future,
+    net::TcpListener,
+    sync::{Arc, Mutex},
+};
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use url::{form_urlencoded, Url};
+
+use crate::{
+    account::Account,
+    models::{
+        BlockMessage, BlockType, ChainData, ChainDataKind, MaintenanceCommand, NodeType,
+        SignedMessage, MAX_TRUST,
+    },
+    responses::{
+        AccountResponse, BankResponse, ConfirmationBlockMessage, UpdatedBalance,
+        ValidatorConfirmationBlockResponse, ValidatorResponse,
+    },
+    testing::ledger::{
+        timestamp, Ledger, LedgerBlock, LedgerConfirmationBlock, LedgerInvalidBlock,
+        LedgerTransaction,
+    },
+};
+
+/// The number of results returned by paginated endpoints when no limit is given
+const DEFAULT_PAGE_LIMIT: usize = 50;
+
+/// State shared by every request to one of the mock nodes
+pub struct MockNode {
+    /// base url the node is listening on
+    pub url: String,
+
+    /// the node's response to the `/config` endpoint
+    pub config: Mutex<Value>,
+
+    /// the key the node signs its messages with, whose account number is the node identifier
+    pub identity: Account,
+
+    /// whether the node is a bank or the primary validator
+    pub node_type: NodeType,
+
+    /// the ledger shared by all the nodes on the mock network
+    pub ledger: Arc<Mutex<Ledger>>,
+
+    /// the banks known by all the nodes on the mock network
+    pub banks: Arc<Mutex<Vec<BankResponse>>>,
+
+    /// the trust the node gives to accounts, which is `"0.00"` for accounts that are not listed
+    pub account_trust: Mutex<HashMap<String, String>>,
+
+    /// the validators known by all the nodes on the mock network
+    pub validators: Arc<Mutex<Vec<ValidatorResponse>>>,
+
+    /// the connection requests the node accepted
+    pub connection_requests: Mutex<Vec<SignedMessage>>,
+
+    /// the state of the node's crawl and clean
+    pub maintenance: Mutex<Maintenance>,
+}
+
+/// The crawl and clean tasks of a mock node
+#[derive(Debug, Default)]
+pub struct Maintenance {
+    /// whether a crawl is running
+    pub crawling: bool,
+
+    /// when the last crawl completed
+    pub last_crawled: Option<String>,
+
+    /// whether a clean is running
+    pub cleaning: bool,
+
+    /// when the last clean completed
+    pub last_cleaned: Option<String>,
+}
+
+/// Serves the node's endpoints on the listener until the returned future is dropped
+pub fn serve(
+    listener: TcpListener,
+    node: Arc<MockNode>,
+) -> impl Future<Output = hyper::Result<()>> {
+    let make_service = make_service_fn(move |_| {
+        let node = node.clone();
+
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let node = node.clone();
+                async move { Ok::<_, Infallible>(handle(&node, req).await) }
+            }))
+        }
+    });
+
+    Server::from_tcp(listener)
+        .expect("the listener is bound to a local address")
+        .serve(make_service)
+}
+
+async fn handle(node: &MockNode, req: Request<Body>) -> Response<Body> {
+    let (parts, body) = req.into_parts();
+    let body = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+
+    let query: Vec<(String, String)> =
+        form_urlencoded::parse(parts.uri.query().unwrap_or("").as_bytes())
+            .into_owned()
+            .collect();
+
+    let segments: Vec<&str> = parts
+        .uri
+        .path()
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    match (&parts.method, node.node_type.clone(), segments.as_slice()) {
+        (&Method::GET, _, ["config"]) => json_response(
+            StatusCode::OK,
+            &*node.config.lock().expect("config lock is not poisoned"),
+        ),
+        (&Method::GET, _, ["accounts"]) => get_accounts(node, parts.uri.path(), &query),
+        (&Method::PATCH, NodeType::BANK, ["accounts", account_number]) => {
+            update_account_trust(node, account_number, &body)
+        }
+        (&Method::GET, _, ["banks"]) => {
+            let banks = node.banks.lock().expect("banks lock is not poisoned");
+            paginated_response(&node.url, parts.uri.path(), &query, &banks)
+        }
+        (&Method::PATCH, NodeType::BANK, ["banks", node_identifier]) => {
+            update_bank_trust(node, node_identifier, &body)
+        }
+        (&Method::GET, _, ["validators"]) => {
+            let validators = node
+                .validators
+                .lock()
+                .expect("validators lock is not poisoned");
+            paginated_response(&node.url, parts.uri.path(), &query, &validators)
+        }
+        (&Method::PATCH, NodeType::BANK, ["validators", node_identifier]) => {
+            update_validator_trust(node, node_identifier, &body)
+        }
+
+        (&Method::POST, _, ["connection_requests"]) => post_connection_request(node, &body),
+        (&Method::GET, _, ["crawl"]) => crawl_response(
+            node,
+            &node
+                .maintenance
+                .lock()
+                .expect("maintenance lock is not poisoned"),
+        ),
+        (&Method::GET, _, ["clean"]) => clean_response(
+            node,
+            &node
+                .maintenance
+                .lock()
+                .expect("maintenance lock is not poisoned"),
+        ),
+        (&Method::POST, _, ["crawl"]) => post_maintenance(node, &body, ChainDataKind::Crawl),
+        (&Method::POST, _, ["clean"]) => post_maintenance(node, &body, ChainDataKind::Clean),
+        (&Method::POST, NodeType::BANK, ["primary_validator_updated"]) => {
+            primary_validator_updated(node, &body)
+        }
+        (&Method::POST, NodeType::PRIMARY_VALIDATOR, ["upgrade_request"]) => {
+            upgrade_request(node, &body)
+        }
+        (&Method::GET, NodeType::BANK, ["bank_transactions"]) => {
+            get_transactions(node, parts.uri.path(), &query)
+        }
+        (&Method::GET, NodeType::BANK, ["confirmation_blocks"]) => {
+            get_confirmation_blocks(node, parts.uri.path(), &query)
+        }
+        (&Method::GET, NodeType::BANK, ["invalid_blocks"]) => {
+            get_invalid_blocks(node, parts.uri.path(), &query)
+        }
+        (&Method::GET, NodeType::BANK, ["blocks"]) => get_blocks(node, parts.uri.path(), &query),
+        (&Method::POST, NodeType::BANK, ["blocks"]) => post_block(node, &body),
+
+        (&Method::GET, NodeType::PRIMARY_VALIDATOR, ["accounts", account_number, "balance"]) => {
+            let ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+            let balance = ledger.account(account_number).map(|acc| acc.balance);
+
+            json_response(StatusCode::OK, &json!({ "balance": balance }))
+        }
+        (
+            &Method::GET,
+            NodeType::PRIMARY_VALIDATOR,
+            ["confirmation_blocks", block_identifier, "valid"],
+        ) => get_valid_confirmation_block(node, block_identifier),
+        (
+            &Method::GET,
+            NodeType::PRIMARY_VALIDATOR,
+            ["accounts", account_number, "balance_lock"],
+        ) => {
+            let ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+            let balance_lock = ledger
+                .account(account_number)
+                .map(|acc| acc.balance_lock.clone());
+
+            json_response(StatusCode::OK, &json!({ "balance_lock": balance_lock }))
+        }
+
+        _ => error_response(StatusCode::NOT_FOUND, "Not found."),
+    }
+}
+
+fn get_transactions(node: &MockNode, path: &str, query: &[(String, String)]) -> Response<Body> {
+    let ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+
+    let matches_filter = |tx: &&LedgerTransaction| {
+        query.iter().all(|(key, value)| match key.as_str() {
+            "account_number" => &tx.recipient == value || &tx.block.sender == value,
+            "recipient" => &tx.recipient == value,
+            "sender" => &tx.block.sender == value,
+            "balance_key" => &tx.block.balance_key == value,
+            "id" => &tx.id == value,
+            "fee" => match value.as_str() {
+                "" | "NONE" => tx.fee.is_none(),
+                fee => tx.fee.as_ref().map(|f| f.to_string()).as_deref() == Some(fee),
+            },
+            _ => true,
+        })
+    };
+
+    let txs: Vec<_> = ledger
+        .transactions()
+        .iter()
+        .filter(matches_filter)
+        .collect();
+
+    paginated_response(&node.url, path, query, &txs)
+}
+
+/// Banks only know the trust of the accounts while validators know their balances
+fn get_accounts(node: &MockNode, path: &str, query: &[(String, String)]) -> Response<Body> {
+    let ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+
+    let account_trust = node
+        .account_trust
+        .lock()
+        .expect("account trust lock is not poisoned");
+
+    let accounts: Vec<_> = ledger
+        .accounts()
+        .into_iter()
+        .map(|(account_number, account)| {
+            let is_bank = node.node_type == NodeType::BANK;
+
+            AccountResponse {
+                id: None,
+                account_number: account_number.to_string(),
+                trust: is_bank.then(|| {
+                    account_trust
+                        .get(account_number)
+                        .cloned()
+                        .unwrap_or_else(|| "0.00".to_string())
+                }),
+                balance: (!is_bank).then_some(account.balance),
+                balance_lock: (!is_bank).then(|| account.balance_lock.clone()),
+            }
+        })
+        .collect();
+
+    paginated_response(&node.url, path, query, &accounts)
+}
+
+/// Only the node's identifier key can change the trust of an account
+fn update_account_trust(node: &MockNode, account_number: &str, body: &[u8]) -> Response<Body> {
+    let trust = match signed_trust(node, body, ChainDataKind::UpdateAccountTrust) {
+        Ok(trust) => trust,
+        Err((status, detail)) => return error_response(status, &detail),
+    };
+
+    let ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+    if ledger.account(account_number).is_none() {
+        return error_response(StatusCode::NOT_FOUND, "Not found.");
+    }
+
+    node.account_trust
+        .lock()
+        .expect("account trust lock is not poisoned")
+        .insert(account_number.to_string(), trust.clone());
+
+    json_response(
+        StatusCode::OK,
+        &AccountResponse {
+            id: None,
+            account_number: account_number.to_string(),
+            trust: Some(trust),
+            balance: None,
+            balance_lock: None,
+        },
+    )
+}
+
+fn update_bank_trust(node: &MockNode, node_identifier: &str, body: &[u8]) -> Response<Body> {
+    let trust = match signed_trust(node, body, ChainDataKind::UpdateBankTrust) {
+        Ok(trust) => trust,
+        Err((status, detail)) => return error_response(status, &detail),
+    };
+
+    let mut banks = node.banks.lock().expect("banks lock is not poisoned");
+    match banks
+        .iter_mut()
+        .find(|bank| bank.node_identifier == node_identifier)
+    {
+        Some(bank) => {
+            bank.trust = trust;
+            json_response(StatusCode::OK, bank)
+        }
+        None => error_response(StatusCode::NOT_FOUND, "Not found."),
+    }
+}
+
+fn update_validator_trust(node: &MockNode, node_identifier: &str, body: &[u8]) -> Response<Body> {
+    let trust = match signed_trust(node, body, ChainDataKind::UpdateValidatorTrust) {
+        Ok(trust) => trust,
+        Err((status, detail)) => return error_response(status, &detail),
+    };
+
+    let mut validators = node
+        .validators
+        .lock()
+        .expect("validators lock is not poisoned");
+    match validators
+        .iter_mut()
+        .find(|validator| validator.node_identifier == node_identifier)
+    {
+        Some(validator) => {
+            validator.trust = trust;
+            json_response(StatusCode::OK, validator)
+        }
+        None => error_response(StatusCode::NOT_FOUND, "Not found."),
+    }
+}
+
+/// The trust requested by a trust message of the kind signed with the node's identifier key,
+/// formatted the way the node stores it
+fn signed_trust(
+    node: &MockNode,
+    body: &[u8],
+    kind: ChainDataKind,
+) -> Result<String, (StatusCode, String)> {
+    let trust = match signed_by_node(node, body, kind)?.message.trust() {
+        Some(trust) if (0..=MAX_TRUST).contains(&trust) => trust,
+        _ => return Err((StatusCode::BAD_REQUEST, "Invalid trust".to_string())),
+    };
+
+    Ok(format!("{:.2}", trust as f64))
+}
+
+/// A message of the kind signed with the node's identifier key
+fn signed_by_node(
+    node: &MockNode,
+    body: &[u8],
+    kind: ChainDataKind,
+) -> Result<SignedMessage, (StatusCode, String)> {
+    let signed_message = parse_signed_message(body, kind)?;
+
+    if !signed_message.verify().unwrap_or(false)
+        || signed_message.node_identifier != node.identity.account_number()
+    {
+        return Err((StatusCode::FORBIDDEN, "Invalid node signature".to_string()));
+    }
+
+    Ok(signed_message)
+}
+
+/// A signed message parsed as the kind of request the endpoint receives
+fn parse_signed_message(
+    body: &[u8],
+    kind: ChainDataKind,
+) -> Result<SignedMessage, (StatusCode, String)> {
+    SignedMessage::parse(&String::from_utf8_lossy(body), kind)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+}
+
+/// The crawl status of the node
+fn crawl_response(node: &MockNode, maintenance: &Maintenance) -> Response<Body> {
+    let config = node.config.lock().expect("config lock is not poisoned");
+    json_response(
+        StatusCode::OK,
+        &json!({
+            "crawl_last_completed": maintenance.last_crawled,
+            "crawl_status": if maintenance.crawling { "crawling" } else { "not_crawling" },
+            "ip_address": config["ip_address"],
+            "port": config["port"],
+            "protocol": config["protocol"],
+        }),
+    )
+}
+
+/// The clean status of the node
+fn clean_response(node: &MockNode, maintenance: &Maintenance) -> Response<Body> {
+    let config = node.config.lock().expect("config lock is not poisoned");
+    json_response(
+        StatusCode::OK,
+        &json!({
+            "clean_last_completed": maintenance.last_cleaned,
+            "clean_status": if maintenance.cleaning { "cleaning" } else { "not_cleaning" },
+            "ip_address": config["ip_address"],
+            "port": config["port"],
+            "protocol": config["protocol"],
+        }),
+    )
+}
+
+/// Starts or stops the node's crawl or clean
+///
+/// The mock nodes have nothing to crawl or clean, so a task runs until it is stopped and completes
+/// as soon as it is stopped.
+fn post_maintenance(node: &MockNode, body: &[u8], kind: ChainDataKind) -> Response<Body> {
+    let signed_message = match signed_by_node(node, body, kind) {
+        Ok(signed_message) => signed_message,
+        Err((status, detail)) => return error_response(status, &detail),
+    };
+
+    let mut maintenance = node
+        .maintenance
+        .lock()
+        .expect("maintenance lock is not poisoned");
+    let Maintenance {
+        crawling,
+        last_crawled,
+        cleaning,
+        last_cleaned,
+    } = &mut *maintenance;
+
+    let (command, running, last_completed, task, status) = match signed_message.message {
+        ChainData::Crawl { crawl } => (crawl, crawling, last_crawled, "crawl", "crawling"),
+        ChainData::Clean { clean } => (clean, cleaning, last_cleaned, "clean", "cleaning"),
+        _ => return error_response(StatusCode::BAD_REQUEST, "Invalid command"),
+    };
+
+    match (command, *running) {
+        (MaintenanceCommand::Start, false) => *running = true,
+        (MaintenanceCommand::Stop, true) => {
+            *running = false;
+            *last_completed = Some(timestamp());
+        }
+        (MaintenanceCommand::Start, true) => {
+            let detail = format!("Can not start new {} when already {}", task, status);
+            return error_response(StatusCode::BAD_REQUEST, &detail);
+        }
+        (MaintenanceCommand::Stop, false) => {
+            let detail = format!("Can not stop {} if not {}", task, status);
+            return error_response(StatusCode::BAD_REQUEST, &detail);
+        }
+    }
+
+    if task == "crawl" {
+        crawl_response(node, &maintenance)
+    } else {
+        clean_response(node, &maintenance)
+    }
+}
+
+/// Switches the bank to the primary validator of another bank, if the bank knows the validator
+fn primary_validator_updated(node: &MockNode, body: &[u8]) -> Response<Body> {
+    let signed_message = match signed_by_bank(node, body, ChainDataKind::PrimaryValidatorUpdated) {
+        Ok(signed_message) => signed_message,
+        Err((status, detail)) => return error_response(status, &detail),
+    };
+
+    let (ip_address, port, protocol) = match signed_message.message {
+        ChainData::PrimaryValidatorUpdated {
+            ip_address,
+            port,
+            protocol,
+        } => (ip_address, port, protocol),
+        _ => return error_response(StatusCode::BAD_REQUEST, "Invalid primary validator"),
+    };
+
+    let validators = node
+        .validators
+        .lock()
+        .expect("validators lock is not poisoned");
+    let validator = validators.iter().find(|validator| {
+        validator.ip_address == ip_address
+            && validator.port == Some(port)
+            && validator.protocol == protocol
+    });
+
+    match validator {
+        Some(validator) => {
+            node.config.lock().expect("config lock is not poisoned")["primary_validator"] =
+                json!(validator);
+            json_response(StatusCode::OK, &json!({}))
+        }
+        None => error_response(StatusCode::BAD_REQUEST, "Networks out of sync"),
+    }
+}
+
+/// Accepts upgrade requests for this validator from the known banks
+///
+/// The mock validator is already the primary validator, so accepting the request changes nothing
+fn upgrade_request(node: &MockNode, body: &[u8]) -> Response<Body> {
+    let signed_message = match signed_by_bank(node, body, ChainDataKind::UpgradeRequest) {
+        Ok(signed_message) => signed_message,
+        Err((status, detail)) => return error_response(status, &detail),
+    };
+
+    match signed_message.message {
+        ChainData::UpgradeRequest {
+            validator_node_identifier,
+        } if validator_node_identifier == node.identity.account_number() => {
+            json_response(StatusCode::OK, &json!({}))
+        }
+        _ => error_response(StatusCode::BAD_REQUEST, "Invalid validator node identifier"),
+    }
+}
+
+/// A message of the kind signed by one of the banks the node knows about
+fn signed_by_bank(
+    node: &MockNode,
+    body: &[u8],
+    kind: ChainDataKind,
+) -> Result<SignedMessage, (StatusCode, String)> {
+    let signed_message = parse_signed_message(body, kind)?;
+
+    if !signed_message.verify().unwrap_or(false) {
+        return Err((StatusCode::BAD_REQUEST, "Invalid signature".to_string()));
+    }
+
+    let known_bank = node
+        .banks
+        .lock()
+        .expect("banks lock is not poisoned")
+        .iter()
+        .any(|bank| bank.node_identifier == signed_message.node_identifier);
+    if !known_bank {
+        return Err((StatusCode::FORBIDDEN, "Unknown bank".to_string()));
+    }
+
+    Ok(signed_message)
+}
+
+/// Accepts the first signed connection request of every node that isn't already known
+fn post_connection_request(node: &MockNode, body: &[u8]) -> Response<Body> {
+    let signed_message = match parse_signed_message(body, ChainDataKind::ConnectionRequest) {
+        Ok(signed_message) => signed_message,
+        Err((status, detail)) => return error_response(status, &detail),
+    };
+
+    if !signed_message.verify().unwrap_or(false) {
+        return error_response(StatusCode::BAD_REQUEST, "Invalid signature");
+    }
+
+    let node_identifier = signed_message.node_identifier.as_str();
+    let mut connection_requests = node
+        .connection_requests
+        .lock()
+        .expect("connection requests lock is not poisoned");
+
+    let already_connected = node_identifier == node.identity.account_number()
+        || node
+            .banks
+            .lock()
+            .expect("banks lock is not poisoned")
+            .iter()
+            .any(|bank| bank.node_identifier == node_identifier)
+        || node
+            .validators
+            .lock()
+            .expect("validators lock is not poisoned")
+            .iter()
+            .any(|validator| validator.node_identifier == node_identifier)
+        || connection_requests
+            .iter()
+            .any(|request| request.node_identifier == node_identifier);
+
+    if already_connected {
+        return error_response(StatusCode::BAD_REQUEST, "Already connected to node");
+    }
+
+    connection_requests.push(signed_message);
+    json_response(StatusCode::CREATED, &json!({}))
+}
+
+fn get_blocks(node: &MockNode, path: &str, query: &[(String, String)]) -> Response<Body> {
+    let ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+
+    let matches_filter = |block: &&LedgerBlock| {
+        query.iter().all(|(key, value)| match key.as_str() {
+            "sender" => &block.sender == value,
+            "balance_key" => &block.balance_key == value,
+            "signature" => &block.signature == value,
+            _ => true,
+        })
+    };
+
+    let blocks: Vec<_> = ledger.blocks().iter().filter(matches_filter).collect();
+
+    paginated_response(&node.url, path, query, &blocks)
+}
+
+fn get_confirmation_blocks(
+    node: &MockNode,
+    path: &str,
+    query: &[(String, String)],
+) -> Response<Body> {
+    let ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+
+    let matches_filter = |confirmation: &&LedgerConfirmationBlock| {
+        query.iter().all(|(key, value)| match key.as_str() {
+            "block" => &confirmation.block == value,
+            "block_identifier" => &confirmation.block_identifier == value,
+            "validator" => &confirmation.validator == value,
+            _ => true,
+        })
+    };
+
+    let confirmations: Vec<_> = ledger
+        .confirmation_blocks()
+        .iter()
+        .filter(matches_filter)
+        .collect();
+
+    paginated_response(&node.url, path, query, &confirmations)
+}
+
+fn get_invalid_blocks(node: &MockNode, path: &str, query: &[(String, String)]) -> Response<Body> {
+    let ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+
+    let matches_filter = |invalid_block: &&LedgerInvalidBlock| {
+        query.iter().all(|(key, value)| match key.as_str() {
+            "block" => &invalid_block.block == value,
+            "block_identifier" => &invalid_block.block_identifier == value,
+            _ => true,
+        })
+    };
+
+    let invalid_blocks: Vec<_> = ledger
+        .invalid_blocks()
+        .iter()
+        .filter(matches_filter)
+        .collect();
+
+    paginated_response(&node.url, path, query, &invalid_blocks)
+}
+
+/// The confirmation block the validator signed, with the current balances of the block's accounts
+fn get_valid_confirmation_block(node: &MockNode, block_identifier: &str) -> Response<Body> {
+    let ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+
+    let block = ledger
+        .confirmation_blocks()
+        .iter()
+        .find(|confirmation| confirmation.block_identifier == block_identifier)
+        .and_then(|confirmation| ledger.block_message(&confirmation.block));
+
+    let block = match block {
+        Some(block) => block.clone(),
+        None => return error_response(StatusCode::NOT_FOUND, "Not found."),
+    };
+
+    let BlockType::CoinTransfer { txs, .. } = &block.message;
+    let mut updated_balances = vec![];
+
+    if let Some(sender) = ledger.account(&block.account_number) {
+        updated_balances.push(UpdatedBalance {
+            account_number: block.account_number.clone(),
+            balance: sender.balance,
+            balance_lock: Some(sender.balance_lock.clone()),
+        });
+    }
+
+    for tx in txs {
+        let is_listed = updated_balances
+            .iter()
+            .any(|updated| updated.account_number == tx.recipient);
+
+        if let (false, Some(recipient)) = (is_listed, ledger.account(&tx.recipient)) {
+            updated_balances.push(UpdatedBalance {
+                account_number: tx.recipient.clone(),
+                balance: recipient.balance,
+                balance_lock: None,
+            });
+        }
+    }
+
+    let message = ConfirmationBlockMessage {
+        block,
+        block_identifier: block_identifier.to_string(),
+        updated_balances,
+    };
+    let signature = node.identity.create_signature(
+        &serde_json::to_string(&message).expect("confirmation blocks can be serialized"),
+    );
+
+    json_response(
+        StatusCode::OK,
+        &ValidatorConfirmationBlockResponse {
+            message,
+            node_identifier: node.identity.account_number().to_string(),
+            signature,
+        },
+    )
+}
+
+fn post_block(node: &MockNode, body: &[u8]) -> Response<Body> {
+    let block_message: BlockMessage = match serde_json::from_slice(body) {
+        Ok(block_message) => block_message,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+
+    let mut ledger = node.ledger.lock().expect("ledger lock is not poisoned");
+
+    match ledger.apply(&block_message) {
+        Ok(block) => json_response(StatusCode::CREATED, &block),
+        Err(rejection) => error_response(StatusCode::BAD_REQUEST, &rejection.description()),
+    }
+}
+
+/// Returns a page of the results with links in the same format as the nodes
+fn paginated_response<T: Serialize>(
+    base_url: &str,
+    path: &str,
+    query: &[(String, String)],
+    results: &[T],
+) -> Response<Body> {
+    let param = |name: &str| {
+        query
+            .iter()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+    };
+
+    let limit = param("limit").unwrap_or(DEFAULT_PAGE_LIMIT).max(1);
+    let offset = param("offset").unwrap_or(0);
+    let count = results.len();
+
+    let page_link = |offset: usize| {
+        let mut params: Vec<(String, String)> = query
+            .iter()
+            .filter(|(key, _)| key != "limit" && key != "offset")
+            .cloned()
+            .collect();
+        params.push(("limit".to_string(), limit.to_string()));
+        params.push(("offset".to_string(), offset.to_string()));
+
+        Url::parse_with_params(&format!("{}{}", base_url, path), params)
+            .expect("the node url is valid")
+            .to_string()
+    };
+
+    let next = if offset + limit < count {
+        Some(page_link(offset + limit))
+    } else {
+        None
+    };
+    let previous = if offset > 0 {
+        Some(page_link(offset.saturating_sub(limit)))
+    } else {
+        None
+    };
+
+    let mut results: Vec<Value> = results
+        .iter()
+        .map(|result| serde_json::to_value(result).expect("mock results can be serialized"))
+        .collect();
+
+    if let Some((_, ordering)) = query.iter().find(|(key, _)| key == "ordering") {
+        let (field, descending) = match ordering.strip_prefix('-') {
+            Some(field) => (field, true),
+            None => (ordering.as_str(), false),
+        };
+
+        results.sort_by(|a, b| compare_fields(&a[field], &b[field]));
+        if descending {
+            results.reverse();
+        }
+    }
+
+    let page: Vec<Value> = results.into_iter().skip(offset).take(limit).collect();
+
+    json_response(
+        StatusCode::OK,
+        &json!({
+            "count": count,
+            "next": next,
+            "previous": previous,
+            "results": page,
+        }),
+    )
+}
+
+/// Compares numbers and decimal strings like `"100.00"` by their value and other fields as strings
+fn compare_fields(a: &Value, b: &Value) -> Ordering {
+    let as_number = |value: &Value| match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) if s.chars().all(|c| c.is_ascii_digit() || c == '.') => s.parse().ok(),
+        _ => None,
+    };
+
+    match (as_number(a), as_number(b)) {
+        (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+fn json_response<T: Serialize>(status: StatusCode, data: &T) -> Response<Body> {
+    let body = serde_json::to_string(data).expect("mock responses can always be serialized");
+
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body))
+        .expect("mock responses are valid")
+}
+
+fn error_response(status: StatusCode, detail: &str) -> Response<Body> {
+    json_response(status, &json!({ "detail": detail }))
+}
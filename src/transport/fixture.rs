@@ -0,0 +1,328 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    error::{Error, Result},
+    transport::{memory::RouteKey, HttpMethod, Transport, TransportResponse},
+};
+
+/// A request and the response the node sent back, as stored in a fixture file
+///
+/// JSON bodies are stored as JSON values so the fixture files can be read and diffed. Bodies that
+/// are not valid JSON are stored as strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The http method of the request
+    pub method: HttpMethod,
+
+    /// The full url of the request
+    pub url: String,
+
+    /// The body of POST and PATCH requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+
+    /// The http status code of the response
+    pub status: u16,
+
+    /// The body of the response
+    pub response_body: Value,
+}
+
+/// The contents of a fixture file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Fixtures {
+    /// Every recorded request in the order it was sent
+    pub interactions: Vec<Interaction>,
+}
+
+/// A [`Transport`] that sends requests through another transport and records every request and
+/// response, so they can be saved to a fixture file and served by a [`ReplayTransport`]
+///
+/// # Example
+/// ```no_run
+///     use std::sync::Arc;
+///     use tnb_rs::{nodes::{RegularNode, ServerNodeTrait}, transport::{RecordingTransport, ReqwestTransport}};
+///
+///     #[tokio::main]
+///     async fn main() {
+///         let recorder = Arc::new(RecordingTransport::new(Arc::new(ReqwestTransport::default())));
+///         let node = RegularNode::with_transport("https://bank.keysign.app", recorder.clone()).unwrap();
+///
+///         node.get_config().await.unwrap();
+///         node.get_transactions(None).await.unwrap();
+///
+///         recorder.save("tests/fixtures/bank.json").unwrap();
+///     }
+/// ```
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    fixtures: Mutex<Fixtures>,
+}
+
+impl RecordingTransport {
+    /// Records the requests sent through the transport
+    pub fn new(inner: Arc<dyn Transport>) -> Self {
+        RecordingTransport {
+            inner,
+            fixtures: Mutex::new(Fixtures::default()),
+        }
+    }
+
+    /// The requests and responses recorded so far
+    pub fn fixtures(&self) -> Fixtures {
+        lock(&self.fixtures).clone()
+    }
+
+    /// The recorded requests and responses as pretty printed json
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&*lock(&self.fixtures))?)
+    }
+
+    /// Writes the recorded requests and responses to a fixture file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        Ok(fs::write(path, self.to_json()? + "\n")?)
+    }
+
+    async fn record(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        body: Option<String>,
+    ) -> Result<TransportResponse> {
+        let response = match &body {
+            None => self.inner.get(url).await?,
+            Some(body) if method == HttpMethod::Patch => {
+                self.inner.patch(url, body.clone()).await?
+            }
+            Some(body) => self.inner.post(url, body.clone()).await?,
+        };
+
+        lock(&self.fixtures).interactions.push(Interaction {
+            method,
+            url: url.to_string(),
+            request_body: body.as_deref().map(to_value),
+            status: response.status,
+            response_body: to_value(&response.body),
+        });
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn get(&self, url: &str) -> Result<TransportResponse> {
+        self.record(HttpMethod::Get, url, None).await
+    }
+
+    async fn post(&self, url: &str, body: String) -> Result<TransportResponse> {
+        self.record(HttpMethod::Post, url, Some(body)).await
+    }
+
+    async fn patch(&self, url: &str, body: String) -> Result<TransportResponse> {
+        self.record(HttpMethod::Patch, url, Some(body)).await
+    }
+}
+
+/// The origin of the node and the route of the request
+type ReplayKey = (String, RouteKey);
+
+/// A [`Transport`] that responds with the responses recorded by a [`RecordingTransport`]
+///
+/// Requests are matched by their method, the node's origin, the path and the query params in any
+/// order. Responses recorded for the same request are replayed in the order they were recorded,
+/// and the last one is repeated after that. Requests that were never recorded return a
+/// [`MissingFixture`](Error::MissingFixture) error.
+///
+/// # Example
+/// ```no_run
+///     use std::sync::Arc;
+///     use tnb_rs::{nodes::{RegularNode, ServerNodeTrait}, transport::ReplayTransport};
+///
+///     #[tokio::main]
+///     async fn main() {
+///         let replay = ReplayTransport::from_file("tests/fixtures/bank.json").unwrap();
+///         let node = RegularNode::with_transport("https://bank.keysign.app", Arc::new(replay)).unwrap();
+///
+///         let config = node.get_config().await.unwrap();
+///     }
+/// ```
+#[derive(Debug)]
+pub struct ReplayTransport {
+    responses: Mutex<HashMap<ReplayKey, VecDeque<TransportResponse>>>,
+}
+
+impl ReplayTransport {
+    /// Replays the recorded requests and responses
+    pub fn new(fixtures: Fixtures) -> Result<Self> {
+        let mut responses: HashMap<_, VecDeque<_>> = HashMap::new();
+
+        for interaction in fixtures.interactions {
+            let body = match interaction.response_body {
+                Value::String(body) => body,
+                json => json.to_string(),
+            };
+
+            responses
+                .entry(replay_key(interaction.method, &interaction.url)?)
+                .or_default()
+                .push_back(TransportResponse {
+                    status: interaction.status,
+                    body,
+                });
+        }
+
+        Ok(ReplayTransport {
+            responses: Mutex::new(responses),
+        })
+    }
+
+    /// Replays the contents of a fixture file
+    pub fn from_json(json: &str) -> Result<Self> {
+        ReplayTransport::new(serde_json::from_str(json)?)
+    }
+
+    /// Reads and replays a fixture file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        ReplayTransport::from_json(&fs::read_to_string(path)?)
+    }
+
+    fn replay(&self, method: HttpMethod, url: &str) -> Result<TransportResponse> {
+        let missing = || Error::MissingFixture(format!("{:?} {}", method, url));
+
+        let mut responses = lock(&self.responses);
+        let queue = responses
+            .get_mut(&replay_key(method, url)?)
+            .ok_or_else(missing)?;
+
+        if queue.len() > 1 {
+            queue.pop_front().ok_or_else(missing)
+        } else {
+            queue.front().cloned().ok_or_else(missing)
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn get(&self, url: &str) -> Result<TransportResponse> {
+        self.replay(HttpMethod::Get, url)
+    }
+
+    async fn post(&self, url: &str, _body: String) -> Result<TransportResponse> {
+        self.replay(HttpMethod::Post, url)
+    }
+
+    async fn patch(&self, url: &str, _body: String) -> Result<TransportResponse> {
+        self.replay(HttpMethod::Patch, url)
+    }
+}
+
+fn replay_key(method: HttpMethod, url: &str) -> Result<ReplayKey> {
+    let origin = Url::parse(url)
+        .map_err(|e| Error::InvalidUrl(e.to_string()))?
+        .origin()
+        .ascii_serialization();
+
+    Ok((origin, RouteKey::new(method, url)?))
+}
+
+fn to_value(body: &str) -> Value {
+    serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.to_string()))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .expect("fixture transport lock is not poisoned")
+}
+
+#[cfg(test)]
+use crate::transport::MemoryTransport;
+
+#[tokio::test]
+async fn record_and_replay_requests() {
+    let memory = Arc::new(MemoryTransport::new());
+    memory
+        .on_get("/config", 200, serde_json::json!({ "node_type": "BANK" }))
+        .on_post(
+            "/blocks",
+            400,
+            serde_json::json!({ "detail": "Invalid block" }),
+        );
+
+    let recorder = RecordingTransport::new(memory);
+    recorder.get("http://127.0.0.1:80/config").await.unwrap();
+    recorder
+        .post(
+            "http://127.0.0.1/blocks",
+            r#"{"signature":"abc"}"#.to_string(),
+        )
+        .await
+        .unwrap();
+
+    let fixtures = recorder.fixtures();
+    assert_eq!(fixtures.interactions.len(), 2);
+    assert_eq!(
+        fixtures.interactions[1].request_body,
+        Some(serde_json::json!({ "signature": "abc" }))
+    );
+
+    let replay = ReplayTransport::from_json(&recorder.to_json().unwrap()).unwrap();
+
+    let response = replay.get("http://127.0.0.1/config/").await.unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, r#"{"node_type":"BANK"}"#);
+
+    let response = replay
+        .post("http://127.0.0.1/blocks", "{}".to_string())
+        .await
+        .unwrap();
+    assert_eq!(response.status, 400);
+
+    // a different node with the same endpoint was never recorded
+    assert!(matches!(
+        replay.get("http://127.0.0.2/config").await,
+        Err(Error::MissingFixture(_))
+    ));
+}
+
+#[tokio::test]
+async fn replay_responses_in_recorded_order() {
+    let interaction = |balance: u64| Interaction {
+        method: HttpMethod::Get,
+        url: "http://127.0.0.1/accounts/abc/balance".to_string(),
+        request_body: None,
+        status: 200,
+        response_body: serde_json::json!({ "balance": balance }),
+    };
+
+    let replay = ReplayTransport::new(Fixtures {
+        interactions: vec![interaction(10), interaction(20)],
+    })
+    .unwrap();
+
+    for expected in [10, 20, 20] {
+        let response = replay
+            .get("http://127.0.0.1/accounts/abc/balance")
+            .await
+            .unwrap();
+        assert_eq!(response.body, format!(r#"{{"balance":{}}}"#, expected));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    error::{Error, Result},
+    transport::{Transport, TransportResponse},
+};
+
+/// The http method of a request sent through a [`Transport`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    /// GET request
+    Get,
+    /// POST request
+    Post,
+    /// PATCH request
+    Patch,
+}
+
+/// A request sent through a [`Transport`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportRequest {
+    /// The http method
+    pub method: HttpMethod,
+
+    /// The full url of the endpoint
+    pub url: String,
+
+    /// The json body of POST and PATCH requests
+    pub body: Option<String>,
+}
+
+/// The path and sorted query params of a url, so the order of the params does not matter
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RouteKey {
+    pub(crate) method: HttpMethod,
+    pub(crate) path: String,
+    pub(crate) query: Vec<(String, String)>,
+}
+
+impl RouteKey {
+    /// Creates a key from a full url or a path with query params
+    pub(crate) fn new(method: HttpMethod, url: &str) -> Result<Self> {
+        let parsed = Url::parse(url)
+            .or_else(|_| Url::parse("http://localhost")?.join(url))
+            .map_err(|e| Error::InvalidUrl(e.to_string()))?;
+
+        let mut query: Vec<(String, String)> = parsed.query_pairs().into_owned().collect();
+        query.sort();
+
+        Ok(RouteKey {
+            method,
+            path: parsed.path().trim_end_matches('/').to_string(),
+            query,
+        })
+    }
+}
+
+/// A [`Transport`] that responds with preset responses instead of sending requests
+///
+/// Responses are matched by the method, path and query params of the request, so the host of the
+/// node is ignored. Requests without a preset response get a `404` response.
+#[derive(Debug, Default)]
+pub struct MemoryTransport {
+    routes: Mutex<HashMap<RouteKey, TransportResponse>>,
+    requests: Mutex<Vec<TransportRequest>>,
+}
+
+impl MemoryTransport {
+    /// Creates a transport without any responses
+    pub fn new() -> Self {
+        MemoryTransport::default()
+    }
+
+    /// Responds to GET requests to the path with the status and the data as the json body
+    ///
+    /// Panics if the path can't be parsed or the data can't be serialized
+    pub fn on_get<D: Serialize>(&self, path: &str, status: u16, data: D) -> &Self {
+        self.on(HttpMethod::Get, path, status, data)
+    }
+
+    /// Responds to POST requests to the path with the status and the data as the json body
+    pub fn on_post<D: Serialize>(&self, path: &str, status: u16, data: D) -> &Self {
+        self.on(HttpMethod::Post, path, status, data)
+    }
+
+    /// Responds to PATCH requests to the path with the status and the data as the json body
+    pub fn on_patch<D: Serialize>(&self, path: &str, status: u16, data: D) -> &Self {
+        self.on(HttpMethod::Patch, path, status, data)
+    }
+
+    /// Responds to requests to the path with the status and the data as the json body
+    pub fn on<D: Serialize>(&self, method: HttpMethod, path: &str, status: u16, data: D) -> &Self {
+        let key = RouteKey::new(method, path).expect("the path is a valid url path");
+        let body = serde_json::to_string(&data).expect("the response data can be serialized");
+
+        lock(&self.routes).insert(key, TransportResponse { status, body });
+        self
+    }
+
+    /// Every request sent through the transport in the order they were sent
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        lock(&self.requests).clone()
+    }
+
+    fn respond(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        body: Option<String>,
+    ) -> Result<TransportResponse> {
+        lock(&self.requests).push(TransportRequest {
+            method,
+            url: url.to_string(),
+            body,
+        });
+
+        let key = RouteKey::new(method, url)?;
+        Ok(lock(&self.routes)
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| TransportResponse {
+                status: 404,
+                body: r#"{"detail":"Not found."}"#.to_string(),
+            }))
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().expect("memory transport lock is not poisoned")
+}
+
+#[async_trait]
+impl Transport for MemoryTransport {
+    async fn get(&self, url: &str) -> Result<TransportResponse> {
+        self.respond(HttpMethod::Get, url, None)
+    }
+
+    async fn post(&self, url: &str, body: String) -> Result<TransportResponse> {
+        self.respond(HttpMethod::Post, url, Some(body))
+    }
+
+    async fn patch(&self, url: &str, body: String) -> Result<TransportResponse> {
+        self.respond(HttpMethod::Patch, url, Some(body))
+    }
+}
+
+#[tokio::test]
+async fn match_requests_by_path_and_query() {
+    let transport = MemoryTransport::new();
+    transport
+        .on_get(
+            "/bank_transactions?limit=1&offset=2",
+            200,
+            serde_json::json!({ "count": 1 }),
+        )
+        .on_post(
+            "/blocks",
+            400,
+            serde_json::json!({ "detail": "Invalid block" }),
+        );
+
+    let response = transport
+        .get("http://127.0.0.1:80/bank_transactions/?offset=2&limit=1")
+        .await
+        .unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, r#"{"count":1}"#);
+
+    let response = transport
+        .get("http://127.0.0.1:80/bank_transactions")
+        .await
+        .unwrap();
+    assert_eq!(response.status, 404);
+
+    let response = transport
+        .post("http://127.0.0.1:80/blocks", "{}".to_string())
+        .await
+        .unwrap();
+    assert_eq!(response.status, 400);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[2].method, HttpMethod::Post);
+    assert_eq!(requests[2].body.as_deref(), Some("{}"));
+}
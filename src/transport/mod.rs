@@ -0,0 +1,97 @@
+//! The http layer the nodes send their requests through
+//!
+//! Every node holds a [`Transport`] that sends the JSON bodies of its requests. The default is
+//! [`ReqwestTransport`], and any other http stack can be used by implementing the trait and
+//! creating the node with `with_transport`.
+//!
+//! [`RecordingTransport`] and [`ReplayTransport`] save the requests a node sends to a fixture file
+//! and serve them back, so code that talks to a live node can be tested without a network.
+//!
+//! # Example
+//! ```
+//!     use std::sync::Arc;
+//!     use serde_json::json;
+//!     use tnb_rs::{nodes::{RegularNode, ServerNodeTrait}, transport::MemoryTransport};
+//!
+//!     #[tokio::main]
+//!     async fn main() {
+//!         let transport = Arc::new(MemoryTransport::new());
+//!         transport.on_get("/config", 200, json!({
+//!             "primary_validator": null,
+//!             "account_number": "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224",
+//!             "ip_address": "127.0.0.1",
+//!             "node_identifier": "57d7a6e732b6280e967666a76a827bf75a0a34ace8ccbc530422c81f8d7b1239",
+//!             "port": 80,
+//!             "protocol": "http",
+//!             "version": "v1.0",
+//!             "default_transaction_fee": 1,
+//!             "node_type": "BANK",
+//!         }));
+//!
+//!         let node = RegularNode::with_transport("http://127.0.0.1", transport.clone()).unwrap();
+//!         let config = node.get_config().await.unwrap();
+//!
+//!         assert_eq!(config.default_transaction_fee, 1);
+//!         assert_eq!(transport.requests().len(), 1);
+//!     }
+//! ```
+
+mod fixture;
+mod memory;
+mod reqwest_transport;
+
+pub use fixture::*;
+pub use memory::*;
+pub use reqwest_transport::*;
+
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+use serde::de;
+
+use crate::error::{Error, Result};
+
+/// The status and body of a node's response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportResponse {
+    /// the http status code
+    pub status: u16,
+
+    /// the raw body of the response
+    pub body: String,
+}
+
+impl TransportResponse {
+    /// Decodes the json body, or returns the status and body if the request was unsuccessful
+    pub fn json<T: de::DeserializeOwned>(self) -> Result<T> {
+        if !(200..300).contains(&self.status) {
+            return Err(Error::HttpStatus {
+                status: self.status,
+                body: self.body,
+            });
+        }
+
+        Ok(serde_json::from_str(&self.body)?)
+    }
+}
+
+/// Sends requests with JSON bodies to the full url of a node's endpoint
+///
+/// Unsuccessful status codes are returned in the [`TransportResponse`]. Errors are only returned
+/// when the request could not be completed.
+#[async_trait]
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Sends a GET request
+    async fn get(&self, url: &str) -> Result<TransportResponse>;
+
+    /// Sends a POST request with a JSON body
+    async fn post(&self, url: &str, body: String) -> Result<TransportResponse>;
+
+    /// Sends a PATCH request with a JSON body
+    async fn patch(&self, url: &str, body: String) -> Result<TransportResponse>;
+}
+
+/// The transport shared by every node created without a config or transport
+pub(crate) fn default_transport() -> Arc<dyn Transport> {
+    ReqwestTransport::shared()
+}
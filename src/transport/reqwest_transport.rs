@@ -0,0 +1,226 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+    Certificate, Method, Proxy,
+};
+
+use crate::{
+    error::{Error, Result},
+    nodes::ClientConfig,
+    transport::{Transport, TransportResponse},
+};
+
+/// The default [`Transport`] that sends requests through a pooled [`reqwest::Client`]
+///
+/// GET requests are retried with an exponential backoff when the node can't be reached or
+/// responds with a server error, up to the [`ClientConfig::max_retries`].
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    max_retries: u32,
+    retry_backoff: Duration,
+}
+
+impl ReqwestTransport {
+    /// Creates a http client with the config's settings
+    pub fn new(config: &ClientConfig) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &config.headers {
+            let invalid_header = || Error::InvalidHeader(name.clone());
+
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid_header())?,
+                HeaderValue::from_str(value).map_err(|_| invalid_header())?,
+            );
+        }
+
+        let mut builder = reqwest::Client::builder()
+            .user_agent(&config.user_agent)
+            .default_headers(headers);
+
+        if let Some(timeout) = config.request_timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        for pem in &config.root_certificates {
+            builder = builder.add_root_certificate(Certificate::from_pem(pem)?);
+        }
+
+        Ok(ReqwestTransport {
+            client: builder.build()?,
+            max_retries: config.max_retries,
+            retry_backoff: config.retry_backoff,
+        })
+    }
+
+    /// The transport shared by every node created without a config or transport
+    pub(crate) fn shared() -> Arc<dyn Transport> {
+        static SHARED: OnceLock<Arc<ReqwestTransport>> = OnceLock::new();
+
+        SHARED
+            .get_or_init(|| {
+                Arc::new(
+                    ReqwestTransport::new(&ClientConfig::new())
+                        .expect("the default client config is valid"),
+                )
+            })
+            .clone()
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<String>,
+    ) -> Result<TransportResponse> {
+        let mut request = self.client.request(method, url);
+        if let Some(body) = body {
+            request = request.header(CONTENT_TYPE, "application/json").body(body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+
+        Ok(TransportResponse {
+            status,
+            body: response.text().await?,
+        })
+    }
+}
+
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        ReqwestTransport::new(&ClientConfig::new()).expect("the default client config is valid")
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn get(&self, url: &str) -> Result<TransportResponse> {
+        let mut backoff = self.retry_backoff;
+        let mut retries = 0;
+
+        loop {
+            let result = self.send(Method::GET, url, None).await;
+
+            let should_retry = match &result {
+                Ok(response) => response.status >= 500 || response.status == 429,
+                Err(_) => true,
+            };
+
+            if !should_retry || retries >= self.max_retries {
+                return result;
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+            retries += 1;
+        }
+    }
+
+    async fn post(&self, url: &str, body: String) -> Result<TransportResponse> {
+        self.send(Method::POST, url, Some(body)).await
+    }
+
+    async fn patch(&self, url: &str, body: String) -> Result<TransportResponse> {
+        self.send(Method::PATCH, url, Some(body)).await
+    }
+}
+
+#[cfg(test)]
+use std::{
+    net::TcpListener,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// Listens on a local port and closes every connection without responding
+#[cfg(test)]
+fn closing_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    let count = connections.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            count.fetch_add(1, Ordering::SeqCst);
+            drop(stream);
+        }
+    });
+
+    (url, connections)
+}
+
+#[tokio::test]
+async fn retry_get_requests_with_backoff() {
+    let (url, connections) = closing_server();
+
+    let mut config = ClientConfig::new();
+    config
+        .max_retries(2)
+        .retry_backoff(Duration::from_millis(10));
+    let transport = ReqwestTransport::new(&config).unwrap();
+
+    assert!(matches!(transport.get(&url).await, Err(Error::Http(_))));
+    assert_eq!(connections.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn post_requests_are_not_retried() {
+    let (url, connections) = closing_server();
+
+    let mut config = ClientConfig::new();
+    config
+        .max_retries(2)
+        .retry_backoff(Duration::from_millis(10));
+    let transport = ReqwestTransport::new(&config).unwrap();
+
+    let result = transport.post(&url, "{}".to_string()).await;
+
+    assert!(matches!(result, Err(Error::Http(_))));
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn request_timeout() {
+    // accepts connections but never responds
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let mut config = ClientConfig::new();
+    config.request_timeout(Duration::from_millis(100));
+    let transport = ReqwestTransport::new(&config).unwrap();
+
+    match transport.get(&url).await {
+        Err(Error::Http(e)) => assert!(e.is_timeout()),
+        result => panic!("expected a timeout but found {:?}", result),
+    }
+}
+
+#[test]
+fn invalid_client_config() {
+    let mut config = ClientConfig::new();
+    config.header("Invalid Header", "value");
+    assert!(matches!(
+        ReqwestTransport::new(&config),
+        Err(Error::InvalidHeader(name)) if name == "Invalid Header"
+    ));
+
+    let mut config = ClientConfig::new();
+    config.root_certificate_pem(b"not a certificate");
+    assert!(matches!(
+        ReqwestTransport::new(&config),
+        Err(Error::Http(_))
+    ));
+}
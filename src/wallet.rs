@@ -1,10 +1,54 @@
+use std::time::{Duration, Instant};
+
 use crate::{
-    account::Account, client::Client, models::Transaction, nodes::RegularNode,
-    responses::BlockResponse,
+    account::Account,
+    client::{BlockStatus, Client},
+    error::{Error, Result},
+    models::Transaction,
+    node_pool::{is_node_failure, NodePool},
+    nodes::{RegularNode, ServerNodeTrait},
+    responses::{BlockResponse, ConfirmationBlockResponse, InvalidBlockResponse},
 };
 
-use anyhow::Result as AnyResult;
-use reqwest::Result;
+/// The time to wait between checks of a block's status
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What happened to a block sent with [`Wallet::send_and_confirm`]
+#[derive(Debug)]
+pub enum SendOutcome {
+    /// The primary validator processed the block, so the account's balance lock has moved past it
+    Confirmed {
+        /// The bank's response to the block
+        block: BlockResponse,
+
+        /// The validator's confirmation, if the bank has received it yet
+        confirmation: Option<ConfirmationBlockResponse>,
+    },
+
+    /// A validator rejected the block
+    Rejected {
+        /// The bank's response to the block
+        block: BlockResponse,
+
+        /// The validator's rejection
+        invalid_block: InvalidBlockResponse,
+    },
+
+    /// The block was neither confirmed nor rejected before the timeout
+    TimedOut {
+        /// The bank's response to the block
+        block: BlockResponse,
+    },
+
+    /// The block was sent, but its status could not be checked
+    Unknown {
+        /// The bank's response to the block
+        block: BlockResponse,
+
+        /// The error returned while checking the block's status
+        error: Error,
+    },
+}
 
 /// Wallet Client for sending transactions on the network
 pub struct Wallet<'a> {
@@ -17,31 +61,44 @@ impl Wallet<'_> {
     /// Create a new Wallet
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     ///     use tnb_rs::{Account, Wallet};
     ///
-    ///     let sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
-    ///     let acc = Account::from_signing_key(sk).unwrap();
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
+    ///         let acc = Account::from_signing_key(sk).unwrap();
     ///
-    ///     let bank_url = "https://bank.keysign.app";
+    ///         let bank_url = "https://bank.keysign.app";
     ///
-    ///     let mut wallet = Wallet::new(&acc, bank_url);
+    ///         let mut wallet = Wallet::new(&acc, bank_url).unwrap();
     ///
-    ///     // This method retrieves the nodes transaction fee details
-    ///     wallet.init();
+    ///         // This method retrieves the nodes transaction fee details
+    ///         wallet.init().await.unwrap();
+    ///     }
     ///
     /// ```
     ///
-    pub fn new<'a>(account: &'a Account, node_url: &'a str) -> Wallet<'a> {
-        Wallet {
-            client: Client::new(node_url),
-            account: account,
-        }
+    pub fn new<'a>(account: &'a Account, node_url: &str) -> Result<Wallet<'a>> {
+        Ok(Wallet {
+            client: Client::new(node_url)?,
+            account,
+        })
+    }
+
+    /// Create a new Wallet that fails over between the nodes in the pool
+    ///
+    /// See [`Client::with_pool`]
+    pub fn with_pool(account: &Account, pool: NodePool) -> Result<Wallet<'_>> {
+        Ok(Wallet {
+            client: Client::with_pool(pool)?,
+            account,
+        })
     }
 
     /// Retrieve/Update the node's config files so you can send transactions
-    pub fn init(&mut self) {
-        self.client.update_config();
+    pub async fn init(&mut self) -> Result<()> {
+        self.client.update_config().await
     }
 
     /// Send a single transaction
@@ -50,30 +107,33 @@ impl Wallet<'_> {
     /// ```no_run
     ///     use tnb_rs::{Account, Wallet, models::Transaction};
     ///
-    ///     let sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
-    ///     let acc = Account::from_signing_key(sk).unwrap();
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
+    ///         let acc = Account::from_signing_key(sk).unwrap();
     ///
-    ///     let bank_url = "https://bank.keysign.app";
+    ///         let bank_url = "https://bank.keysign.app";
     ///
-    ///     let mut wallet = Wallet::new(&acc, bank_url);
+    ///         let mut wallet = Wallet::new(&acc, bank_url).unwrap();
     ///
-    ///     // This method retrieves the nodes transaction fee details
-    ///     // It is important you call this method before sending a transaction
-    ///     wallet.init();
+    ///         // This method retrieves the nodes transaction fee details
+    ///         // It is important you call this method before sending a transaction
+    ///         wallet.init().await.unwrap();
     ///
-    ///     let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
-    ///     let tx = Transaction::new(recipient, 1000);
+    ///         let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+    ///         let tx = Transaction::new(recipient, 1000);
     ///
-    ///     let block = wallet.send_transaction(&tx).unwrap();
+    ///         let block = wallet.send_transaction(&tx).await.unwrap();
     ///
-    ///     println!("block response: {:?}", block);
+    ///         println!("block response: {:?}", block);
+    ///     }
     ///
     /// ```
     ///
-    pub fn send_transaction<'a>(&'a self, tx: &'a Transaction) -> Result<BlockResponse> {
-        let txs = vec![(*tx).clone()];
+    pub async fn send_transaction(&self, tx: &Transaction) -> Result<BlockResponse> {
+        let txs = vec![tx.clone()];
 
-        self.client.send_transactions(self.account, &txs)
+        self.client.send_transactions(self.account, &txs).await
     }
 
     /// Send multiple transactions
@@ -84,59 +144,493 @@ impl Wallet<'_> {
     /// ```no_run
     ///     use tnb_rs::{Account, Wallet, models::Transaction};
     ///
-    ///     let sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
-    ///     let acc = Account::from_signing_key(sk).unwrap();
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
+    ///         let acc = Account::from_signing_key(sk).unwrap();
     ///
-    ///     let bank_url = "https://bank.keysign.app";
+    ///         let bank_url = "https://bank.keysign.app";
     ///
-    ///     let mut wallet = Wallet::new(&acc, bank_url);
+    ///         let mut wallet = Wallet::new(&acc, bank_url).unwrap();
     ///
-    ///     wallet.init();
+    ///         wallet.init().await.unwrap();
     ///
-    ///     let recipient1 = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
-    ///     let recipient2 = Account::new();
-    ///     let recipient3 = Account::new();
+    ///         let recipient1 = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+    ///         let recipient2 = Account::new();
+    ///         let recipient3 = Account::new();
     ///
-    ///     let tx1 = Transaction::new(recipient1, 1000);
-    ///     let tx2 = Transaction::new(recipient2.account_number(), 1000);
-    ///     let tx3 = Transaction::new_with_memo(recipient3.account_number(), 1000, "Testing tnb_rs");
+    ///         let tx1 = Transaction::new(recipient1, 1000);
+    ///         let tx2 = Transaction::new(recipient2.account_number(), 1000);
+    ///         let tx3 = Transaction::new_with_memo(recipient3.account_number(), 1000, "Testing tnb_rs");
     ///
-    ///     let txs = vec![tx1, tx2, tx3];
+    ///         let txs = vec![tx1, tx2, tx3];
     ///
-    ///     let block = wallet.send_transactions(&txs);
+    ///         let block = wallet.send_transactions(&txs).await;
     ///
-    ///     println!("block response: {:?}", block);
+    ///         println!("block response: {:?}", block);
+    ///     }
     ///
     /// ```
     ///
-    pub fn send_transactions<'a>(&'a self, txs: &'a Vec<Transaction>) -> Result<BlockResponse> {
-        self.client.send_transactions(self.account, txs)
+    pub async fn send_transactions(&self, txs: &[Transaction]) -> Result<BlockResponse> {
+        self.client.send_transactions(self.account, txs).await
+    }
+
+    /// Send multiple transactions and wait until the block is confirmed or rejected
+    ///
+    /// The block's status and the account's balance lock are checked until the balance lock
+    /// moves past the block's balance key, a validator rejects the block or `timeout` expires.
+    /// The block stays pending while the primary validator has no balance lock for the account.
+    /// Checks that fail because a node is down are retried until the timeout, and other errors
+    /// are returned with the block in [`SendOutcome::Unknown`], so the sent block is never lost.
+    ///
+    /// # Example
+    /// ```no_run
+    ///     use std::time::Duration;
+    ///     use tnb_rs::{Account, SendOutcome, Wallet, models::Transaction};
+    ///
+    ///     #[tokio::main]
+    ///     async fn main() {
+    ///         let sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
+    ///         let acc = Account::from_signing_key(sk).unwrap();
+    ///
+    ///         let mut wallet = Wallet::new(&acc, "https://bank.keysign.app").unwrap();
+    ///         wallet.init().await.unwrap();
+    ///
+    ///         let recipient = "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224";
+    ///         let txs = vec![Transaction::new(recipient, 1000)];
+    ///
+    ///         match wallet.send_and_confirm(&txs, Duration::from_secs(30)).await.unwrap() {
+    ///             SendOutcome::Confirmed { block, .. } => println!("confirmed {}", block.id),
+    ///             SendOutcome::Rejected { invalid_block, .. } => println!("rejected: {:?}", invalid_block),
+    ///             SendOutcome::TimedOut { block } => println!("{} is still pending", block.id),
+    ///             SendOutcome::Unknown { block, error } => println!("{} was sent: {}", block.id, error),
+    ///         }
+    ///     }
+    /// ```
+    pub async fn send_and_confirm(
+        &self,
+        txs: &[Transaction],
+        timeout: Duration,
+    ) -> Result<SendOutcome> {
+        let block = self.client.send_transactions(self.account, txs).await?;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match self.client.block_status(&block.id).await {
+                Ok(BlockStatus::Invalid(invalid_block)) => {
+                    return Ok(SendOutcome::Rejected {
+                        block,
+                        invalid_block,
+                    })
+                }
+                Ok(BlockStatus::Confirmed(confirmation)) => {
+                    return Ok(SendOutcome::Confirmed {
+                        block,
+                        confirmation: Some(confirmation),
+                    })
+                }
+                Ok(BlockStatus::Pending) => {}
+                Err(e) if is_node_failure(&e) => {}
+                Err(error) => return Ok(SendOutcome::Unknown { block, error }),
+            }
+
+            match self.client.get_account_balance_lock(self.account).await {
+                // an account the primary validator doesn't know yet is still pending
+                Ok(Some(balance_lock)) if balance_lock != block.balance_key => {
+                    return Ok(SendOutcome::Confirmed {
+                        block,
+                        confirmation: None,
+                    });
+                }
+                Ok(_) => {}
+                Err(e) if is_node_failure(&e) => {}
+                Err(error) => return Ok(SendOutcome::Unknown { block, error }),
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(SendOutcome::TimedOut { block });
+            }
+
+            tokio::time::sleep(CONFIRMATION_POLL_INTERVAL.min(deadline - now)).await;
+        }
     }
 
     /// Get the number of coins held in an account
-    pub fn get_balance(&self) -> AnyResult<Option<u64>> {
+    pub async fn get_balance(&self) -> Result<Option<u64>> {
         self.client
-            .get_account_balance(&self.account.account_number())
+            .get_account_balance(self.account.account_number())
+            .await
     }
 
     /// Switch to a different node to process transaction
-    pub fn switch_node<'a>(&'a mut self, node_url: &'a str) -> Result<()> {
-        self.client.node = RegularNode::new(node_url);
-        self.init();
-        Ok(())
+    ///
+    /// The new node sends requests through the same transport as the current one. Wallets created
+    /// with [`Wallet::with_pool`] always use the healthiest node in the pool, so switching their
+    /// node returns an error.
+    pub async fn switch_node(&mut self, node_url: &str) -> Result<()> {
+        if self.client.pool().is_some() {
+            return Err(Error::PooledNodeSwitch);
+        }
+
+        let transport = self.client.node.get_base().transport().clone();
+        self.client.node = RegularNode::with_transport(node_url, transport)?;
+
+        // the new node may have selected a different primary validator
+        self.client.invalidate_primary_validator();
+        self.init().await
+    }
+}
+
+#[cfg(test)]
+use crate::{
+    testing::{MockNetwork, MOCK_BANK_FEE, MOCK_PRIMARY_VALIDATOR_FEE},
+    transport::{Transport, TransportResponse},
+};
+#[cfg(test)]
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Responds to the first requests for blocks that are not sent yet like a node that is down
+#[cfg(test)]
+#[derive(Debug)]
+struct FlakyTransport {
+    inner: Arc<dyn Transport>,
+    failures: AtomicUsize,
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl Transport for FlakyTransport {
+    async fn get(&self, url: &str) -> Result<TransportResponse> {
+        let failing = self.failures.load(Ordering::SeqCst) > 0;
+        if failing && url.contains("/invalid_blocks") {
+            self.failures.fetch_sub(1, Ordering::SeqCst);
+            return Ok(TransportResponse {
+                status: 503,
+                body: "Service unavailable".to_string(),
+            });
+        }
+
+        self.inner.get(url).await
+    }
+
+    async fn post(&self, url: &str, body: String) -> Result<TransportResponse> {
+        self.inner.post(url, body).await
+    }
+
+    async fn patch(&self, url: &str, body: String) -> Result<TransportResponse> {
+        self.inner.patch(url, body).await
+    }
+}
+
+/// Responds like a primary validator that has no balance lock for the sender once a block is sent
+#[cfg(test)]
+#[derive(Debug)]
+struct UnknownAccountTransport {
+    inner: Arc<dyn Transport>,
+    block_sent: AtomicBool,
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl Transport for UnknownAccountTransport {
+    async fn get(&self, url: &str) -> Result<TransportResponse> {
+        if self.block_sent.load(Ordering::SeqCst) && url.ends_with("/balance_lock") {
+            return Ok(TransportResponse {
+                status: 200,
+                body: r#"{"balance_lock":null}"#.to_string(),
+            });
+        }
+
+        self.inner.get(url).await
+    }
+
+    async fn post(&self, url: &str, body: String) -> Result<TransportResponse> {
+        let response = self.inner.post(url, body).await;
+        if url.ends_with("/blocks") {
+            self.block_sent.store(true, Ordering::SeqCst);
+        }
+
+        response
+    }
+
+    async fn patch(&self, url: &str, body: String) -> Result<TransportResponse> {
+        self.inner.patch(url, body).await
     }
 }
 
-#[test]
-fn get_balance() {
+#[tokio::test]
+async fn get_balance() {
+    let network = MockNetwork::start();
     let sk = "4b3e69add153435a30c03f6ba4576cedeacfd9d362272a39863f0f3e37eda72c";
     let acc = Account::from_signing_key(sk).unwrap();
-    let node_url = "http://bank.tnbexplorer.com";
+    network.fund_account(acc.account_number(), 1_000);
 
-    let mut wallet = Wallet::new(&acc, node_url);
-    wallet.init();
+    let mut wallet = Wallet::new(&acc, network.bank_url()).unwrap();
+    wallet.init().await.unwrap();
 
-    let balance = wallet.get_balance().unwrap();
+    let balance = wallet.get_balance().await.unwrap();
 
     println!("balance: {:?}", balance);
+    assert_eq!(balance, Some(1_000));
+}
+
+#[tokio::test]
+async fn send_transaction() {
+    let network = MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 1_000);
+
+    let mut wallet = Wallet::new(&acc, network.bank_url()).unwrap();
+    wallet.init().await.unwrap();
+
+    let recipient = Account::new();
+    let tx = Transaction::new_with_memo(recipient.account_number(), 100, "Mock network");
+
+    let block = wallet.send_transaction(&tx).await.unwrap();
+    assert_eq!(block.sender, acc.account_number());
+    assert_eq!(block.balance_key, acc.account_number());
+
+    let fees = MOCK_BANK_FEE + MOCK_PRIMARY_VALIDATOR_FEE;
+    assert_eq!(
+        wallet.get_balance().await.unwrap(),
+        Some(1_000 - 100 - fees)
+    );
+    assert_eq!(network.balance(recipient.account_number()), Some(100));
+
+    let new_balance_lock = network.balance_lock(acc.account_number()).unwrap();
+    assert_ne!(new_balance_lock, acc.account_number());
+
+    // the next block has to use the new balance lock
+    let block = wallet.send_transaction(&tx).await.unwrap();
+    assert_eq!(block.balance_key, new_balance_lock);
+    assert_eq!(network.balance(recipient.account_number()), Some(200));
+}
+
+#[tokio::test]
+async fn send_and_confirm() {
+    let network = MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 1_000);
+
+    let mut wallet = Wallet::new(&acc, network.bank_url()).unwrap();
+    wallet.init().await.unwrap();
+
+    let txs = vec![Transaction::new(Account::new().account_number(), 100)];
+    let timeout = Duration::from_secs(5);
+
+    // the mock primary validator processes blocks as soon as the bank accepts them
+    let outcome = wallet.send_and_confirm(&txs, timeout).await.unwrap();
+    assert!(matches!(
+        outcome,
+        SendOutcome::Confirmed {
+            confirmation: None,
+            ..
+        }
+    ));
+
+    network.hold_blocks();
+    let outcome = wallet
+        .send_and_confirm(&txs, Duration::from_millis(200))
+        .await
+        .unwrap();
+
+    let block = match outcome {
+        SendOutcome::TimedOut { block } => block,
+        outcome => panic!("expected the block to time out but found {:?}", outcome),
+    };
+
+    network.confirm_block(&block.id).unwrap();
+    assert!(matches!(
+        wallet.client.block_status(&block.id).await.unwrap(),
+        BlockStatus::Confirmed(_)
+    ));
+}
+
+#[tokio::test]
+async fn send_and_confirm_rejected_block() {
+    let network = MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 1_000);
+
+    let mut wallet = Wallet::new(&acc, network.bank_url()).unwrap();
+    wallet.init().await.unwrap();
+
+    network.hold_blocks();
+
+    let reject_sent_block = async {
+        loop {
+            let sent = network
+                .ledger()
+                .blocks()
+                .iter()
+                .find(|block| block.sender == acc.account_number())
+                .cloned();
+
+            if let Some(block) = sent {
+                network.invalidate_block(&block.id).unwrap();
+                return;
+            }
+
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    };
+
+    let txs = vec![Transaction::new(Account::new().account_number(), 100)];
+    let (outcome, _) = tokio::join!(
+        wallet.send_and_confirm(&txs, Duration::from_secs(5)),
+        reject_sent_block
+    );
+
+    assert!(matches!(
+        outcome.unwrap(),
+        SendOutcome::Rejected { block, invalid_block } if invalid_block.block == block.id
+    ));
+}
+
+#[tokio::test]
+async fn switch_node() {
+    let network = MockNetwork::start();
+    let other_network = MockNetwork::start();
+    let acc = Account::new();
+    other_network.fund_account(acc.account_number(), 1_000);
+
+    let mut wallet = Wallet::new(&acc, network.bank_url()).unwrap();
+    wallet.init().await.unwrap();
+    assert_eq!(wallet.get_balance().await.unwrap(), None);
+
+    // balances are read from the primary validator of the new node
+    wallet.switch_node(other_network.bank_url()).await.unwrap();
+    assert_eq!(wallet.get_balance().await.unwrap(), Some(1_000));
+
+    let pool = NodePool::new(&[network.bank_url()]).unwrap();
+    let mut pooled_wallet = Wallet::with_pool(&acc, pool).unwrap();
+    assert!(matches!(
+        pooled_wallet.switch_node(other_network.bank_url()).await,
+        Err(Error::PooledNodeSwitch)
+    ));
+}
+
+#[tokio::test]
+async fn send_and_confirm_retries_unavailable_node() {
+    let network = MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 1_000);
+
+    let transport = Arc::new(FlakyTransport {
+        inner: crate::transport::default_transport(),
+        failures: AtomicUsize::new(2),
+    });
+
+    let mut wallet = Wallet::new(&acc, network.bank_url()).unwrap();
+    wallet.client.node =
+        RegularNode::with_transport(network.bank_url(), transport.clone()).unwrap();
+    wallet.init().await.unwrap();
+
+    network.hold_blocks();
+
+    // the block is confirmed once the node has failed the status checks
+    let confirm_sent_block = async {
+        while transport.failures.load(Ordering::SeqCst) > 0 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let block_id = network.ledger().blocks().last().unwrap().id.clone();
+        network.confirm_block(&block_id).unwrap();
+    };
+
+    let txs = vec![Transaction::new(Account::new().account_number(), 100)];
+    let (outcome, _) = tokio::join!(
+        wallet.send_and_confirm(&txs, Duration::from_secs(5)),
+        confirm_sent_block
+    );
+
+    assert!(matches!(
+        outcome.unwrap(),
+        SendOutcome::Confirmed {
+            confirmation: Some(_),
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn send_and_confirm_without_balance_lock() {
+    let network = MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 1_000);
+
+    let transport = Arc::new(UnknownAccountTransport {
+        inner: crate::transport::default_transport(),
+        block_sent: AtomicBool::new(false),
+    });
+
+    let mut wallet = Wallet::new(&acc, network.bank_url()).unwrap();
+    wallet.client.node = RegularNode::with_transport(network.bank_url(), transport).unwrap();
+    wallet.init().await.unwrap();
+
+    network.hold_blocks();
+
+    // a missing balance lock doesn't prove that the block was processed
+    let txs = vec![Transaction::new(Account::new().account_number(), 100)];
+    let outcome = wallet
+        .send_and_confirm(&txs, Duration::from_millis(200))
+        .await
+        .unwrap();
+
+    assert!(matches!(outcome, SendOutcome::TimedOut { .. }));
+}
+
+#[tokio::test]
+async fn send_and_confirm_rejected_block_through_node_pool() {
+    let network = MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 1_000);
+
+    let down = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let down = format!("http://{}", down);
+
+    let pool = NodePool::new(&[network.bank_url(), &down]).unwrap();
+    let mut wallet = Wallet::with_pool(&acc, pool).unwrap();
+    wallet.init().await.unwrap();
+
+    // the selected node goes down after the wallet was initialized
+    wallet.client.node = RegularNode::new(&down).unwrap();
+    network.hold_blocks();
+
+    let reject_sent_block = async {
+        loop {
+            let sent = network
+                .ledger()
+                .blocks()
+                .iter()
+                .find(|block| block.sender == acc.account_number())
+                .cloned();
+
+            if let Some(block) = sent {
+                network.invalidate_block(&block.id).unwrap();
+                return;
+            }
+
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    };
+
+    let txs = vec![Transaction::new(Account::new().account_number(), 100)];
+    let (outcome, _) = tokio::join!(
+        wallet.send_and_confirm(&txs, Duration::from_secs(5)),
+        reject_sent_block
+    );
+
+    assert!(matches!(
+        outcome.unwrap(),
+        SendOutcome::Rejected { block, invalid_block } if invalid_block.block == block.id
+    ));
 }
@@ -13,32 +13,38 @@ fn generates_random_account() {
 #[test]
 fn generate_account_from_invalid_signing_key() {
     let acc = Account::from_signing_key("abcdefghijklmnop");
-    assert_eq!(acc.is_err(), true);
+    assert!(acc.is_err());
+}
+
+#[test]
+fn generate_account_from_signing_key_with_invalid_length() {
+    let acc = Account::from_signing_key(&SIGNING_KEY_HEX[..60]);
+    assert!(matches!(
+        acc,
+        Err(Error::InvalidKeyLength {
+            expected: 64,
+            found: 60
+        })
+    ));
 }
 
 #[test]
 fn generate_account_from_valid_signing_key() {
     let acc = Account::from_signing_key(SIGNING_KEY_HEX);
-    assert_eq!(acc.is_ok(), true);
+    assert!(acc.is_ok());
 
     let acc = acc.unwrap();
     assert_eq!(acc.account_number().len(), 64);
     assert_eq!(acc.signing_key().len(), 64);
 
     assert_eq!(acc.account_number(), ACCOUNT_NUMBER_HEX);
-    assert_eq!(acc.signing_key(), SIGNING_KEY_HEX);
+    assert_eq!(acc.signing_key().as_str(), SIGNING_KEY_HEX);
 }
 
 #[test]
 fn is_valid_keypair() {
-    assert_eq!(
-        Account::is_valid_keypair(SIGNING_KEY_HEX, ACCOUNT_NUMBER_HEX),
-        true
-    );
-    assert_eq!(
-        Account::is_valid_keypair(ACCOUNT_NUMBER_HEX, SIGNING_KEY_HEX),
-        false
-    );
+    assert!(Account::is_valid_keypair(SIGNING_KEY_HEX, ACCOUNT_NUMBER_HEX).unwrap());
+    assert!(!Account::is_valid_keypair(ACCOUNT_NUMBER_HEX, SIGNING_KEY_HEX).unwrap());
 }
 
 #[test]
@@ -59,7 +65,7 @@ fn create_tx_signature() {
 
     let tx = Transaction {
         amount: 1,
-        recipient: "000000000000000000000000000000000000000000000000000000000000dead",
+        recipient: "000000000000000000000000000000000000000000000000000000000000dead".to_string(),
         memo: None,
         fee: None,
     };
@@ -76,20 +82,35 @@ fn create_and_verify_signature() {
     let message = "testing create signature";
     let sig = acc.create_signature(message);
     assert_eq!(sig.len(), 128);
-    let result = Account::verify_signature(&sig, message, &ACCOUNT_NUMBER_HEX);
-    assert_eq!(result, true);
+    let result = Account::verify_signature(&sig, message, ACCOUNT_NUMBER_HEX).unwrap();
+    assert!(result);
     // Testing with wrong message
-    assert_eq!(
-        Account::verify_signature(&sig, "testing create", &ACCOUNT_NUMBER_HEX),
-        false
-    );
+    assert!(!Account::verify_signature(&sig, "testing create", ACCOUNT_NUMBER_HEX).unwrap());
     // Testing with wrong Account number
-    assert_eq!(
-        Account::verify_signature(&sig, "testing create", &Account::new().account_number()),
-        false
+    assert!(
+        !Account::verify_signature(&sig, "testing create", Account::new().account_number())
+            .unwrap()
     );
 }
 
+#[test]
+fn verify_signature_with_invalid_hex() {
+    let acc = Account::from_signing_key(SIGNING_KEY_HEX).unwrap();
+    let sig = acc.create_signature("message");
+
+    assert!(matches!(
+        Account::verify_signature("not a signature", "message", ACCOUNT_NUMBER_HEX),
+        Err(Error::InvalidHex(_))
+    ));
+    assert!(matches!(
+        Account::verify_signature(&sig, "message", &ACCOUNT_NUMBER_HEX[..62]),
+        Err(Error::InvalidKeyLength {
+            expected: 64,
+            found: 62
+        })
+    ));
+}
+
 #[test]
 fn create_block_message() {
     let acc = Account::from_signing_key(
@@ -100,21 +121,21 @@ fn create_block_message() {
     let tx = Transaction {
         amount: 1,
         fee: None,
-        recipient: "000000000000000000000000000000000000000000000000000000000000dead",
+        recipient: "000000000000000000000000000000000000000000000000000000000000dead".to_string(),
         memo: None,
     };
 
     let node_fee = Transaction {
         amount: 1,
         fee: Some(NodeType::BANK),
-        recipient: "29865762fae7d26e51f6465b3fea436d513478cfb8aa068e88a927e887cdc5fc",
+        recipient: "29865762fae7d26e51f6465b3fea436d513478cfb8aa068e88a927e887cdc5fc".to_string(),
         memo: None,
     };
 
     let pv_fee = Transaction {
         amount: 1,
         fee: Some(NodeType::PRIMARY_VALIDATOR),
-        recipient: "ec8f6734272e8d9d5ea995479dd6d173424be38b313a3069d5fa62e7038a08e9",
+        recipient: "ec8f6734272e8d9d5ea995479dd6d173424be38b313a3069d5fa62e7038a08e9".to_string(),
         memo: None,
     };
 
@@ -122,19 +143,45 @@ fn create_block_message() {
     let balance_key =
         "1329d3a5d4a5ec2382dc539e03f30c3760e01932834a23522d3de0393b63f224".to_string();
 
-    let block_data = BlockType::coin_transfer(balance_key, txs);
-    let block_message = acc.create_block_message(&block_data);
+    let block_data = BlockType::coin_transfer(balance_key, txs).unwrap();
+    let block_message = acc.create_block_message(&block_data).unwrap();
 
     let serialized_data = serde_json::to_string(&block_data).unwrap();
 
     println!("block_message: {:?}\n\n", block_message);
 
-    assert_eq!(
-        Account::verify_signature(
-            &block_message.signature,
-            &serialized_data,
-            acc.account_number()
-        ),
-        true
-    );
+    assert!(Account::verify_signature(
+        &block_message.signature,
+        &serialized_data,
+        acc.account_number()
+    )
+    .unwrap());
+}
+
+#[test]
+fn account_keystore() {
+    let acc = Account::from_signing_key(SIGNING_KEY_HEX).unwrap();
+    let keystore = acc.to_keystore("password").unwrap();
+
+    assert!(keystore.contains(ACCOUNT_NUMBER_HEX));
+    assert!(!keystore.contains(SIGNING_KEY_HEX));
+
+    let restored_acc = Account::from_keystore(&keystore, "password").unwrap();
+    assert_eq!(restored_acc.signing_key().as_str(), SIGNING_KEY_HEX);
+
+    assert!(matches!(
+        Account::from_keystore(&keystore, "wrong password"),
+        Err(Error::IncorrectPassword)
+    ));
+}
+
+#[test]
+fn debug_output_redacts_signing_key() {
+    let acc = Account::from_signing_key(SIGNING_KEY_HEX).unwrap();
+
+    let debug = format!("{:?}", acc);
+    assert!(debug.contains(ACCOUNT_NUMBER_HEX));
+    assert!(!debug.contains(SIGNING_KEY_HEX));
+
+    assert!(format!("{:?}", acc.reveal_secrets()).contains(SIGNING_KEY_HEX));
 }
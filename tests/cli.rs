@@ -0,0 +1,216 @@
+#![cfg(feature = "cli")]
+
+use std::process::{Command, Output};
+
+use serde_json::Value;
+use tnb_rs::*;
+
+const MNEMONIC: &str =
+    "visa nephew like this amazing soldier negative front elevator warfare teach good";
+
+fn tnb(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_tnb"))
+        .arg("--json")
+        .args(args)
+        .env_remove("TNB_SIGNING_KEY")
+        .env_remove("TNB_MNEMONIC")
+        .env_remove("TNB_MNEMONIC_PASSWORD")
+        .env_remove("TNB_NODE")
+        .output()
+        .expect("failed to run the tnb binary")
+}
+
+fn tnb_json(args: &[&str]) -> Value {
+    let output = tnb(args);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn keygen() {
+    let output = tnb_json(&["keygen"]);
+
+    let acc = Account::from_signing_key(output["signing_key"].as_str().unwrap()).unwrap();
+    assert_eq!(output["account_number"], acc.account_number());
+    assert!(output.get("mnemonic").is_none());
+
+    let output = tnb_json(&["keygen", "--mnemonic"]);
+    let hd = HDWallet::from_mnemonic(output["mnemonic"].as_str().unwrap(), None).unwrap();
+    assert_eq!(
+        output["account_number"],
+        hd.get_first_account().unwrap().account_number()
+    );
+}
+
+#[test]
+fn derive_account() {
+    let hd = HDWallet::from_mnemonic(MNEMONIC, Some("password")).unwrap();
+    let acc = hd.get_account(2, 7).unwrap();
+
+    let output = tnb_json(&[
+        "derive",
+        "--mnemonic",
+        MNEMONIC,
+        "--password",
+        "password",
+        "--account-index",
+        "2",
+        "--address-index",
+        "7",
+    ]);
+
+    assert_eq!(output["account_number"], acc.account_number());
+    assert_eq!(output["signing_key"], acc.signing_key().as_str());
+
+    // the mnemonic was given by the user, so it isn't printed again
+    assert!(output.get("mnemonic").is_none());
+
+    let output = tnb_json(&["restore", "--mnemonic", MNEMONIC]);
+    assert_eq!(output["mnemonic"], MNEMONIC);
+    assert_eq!(
+        output["seed"],
+        HDWallet::from_mnemonic(MNEMONIC, None)
+            .unwrap()
+            .seed_hex()
+            .as_str()
+    );
+}
+
+#[test]
+fn sign_and_verify_message() {
+    let acc = Account::new();
+    let signing_key = acc.signing_key();
+
+    let output = tnb_json(&["sign", "--signing-key", signing_key.as_str(), "hello"]);
+    let signature = output["signature"].as_str().unwrap();
+    assert_eq!(signature, acc.create_signature("hello"));
+
+    let output = tnb_json(&["verify", acc.account_number(), signature, "hello"]);
+    assert_eq!(output["valid"], true);
+
+    let output = tnb(&["verify", acc.account_number(), signature, "goodbye"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn invalid_signing_key() {
+    let output = tnb(&["sign", "--signing-key", "abcd", "hello"]);
+    assert!(!output.status.success());
+
+    let error: Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert!(error["error"].is_string());
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn send_and_list_transactions() {
+    let network = testing::MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 1_000);
+
+    let signing_key = acc.signing_key();
+    let (recipient1, recipient2) = (Account::new(), Account::new());
+
+    tnb_json(&[
+        "send",
+        "--signing-key",
+        signing_key.as_str(),
+        "--node",
+        network.bank_url(),
+        recipient1.account_number(),
+        "100",
+        "--memo",
+        "rent",
+    ]);
+
+    let tx1 = format!("{}:20", recipient1.account_number());
+    let tx2 = format!("{}:30:gift card", recipient2.account_number());
+    tnb_json(&[
+        "send-many",
+        "--signing-key",
+        signing_key.as_str(),
+        "--node",
+        network.bank_url(),
+        "--tx",
+        &tx1,
+        "--tx",
+        &tx2,
+    ]);
+
+    assert_eq!(network.balance(recipient1.account_number()), Some(120));
+    assert_eq!(network.balance(recipient2.account_number()), Some(30));
+
+    let output = tnb_json(&[
+        "balance",
+        "--node",
+        network.bank_url(),
+        recipient1.account_number(),
+    ]);
+    assert_eq!(output["balance"], 120);
+
+    let output = tnb_json(&[
+        "transactions",
+        "--node",
+        network.bank_url(),
+        "--sender",
+        acc.account_number(),
+        "--fee",
+        "none",
+    ]);
+    assert_eq!(output["count"], 3);
+
+    let output = tnb_json(&[
+        "transactions",
+        "--node",
+        network.bank_url(),
+        "--recipient",
+        recipient2.account_number(),
+    ]);
+    assert_eq!(output["results"][0]["memo"], "gift card");
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn apply_trust_table() {
+    let network = testing::MockNetwork::start();
+    let acc = Account::new();
+    network.fund_account(acc.account_number(), 10);
+
+    let table = std::env::temp_dir().join(format!("tnb-trust-{}.json", acc.account_number()));
+    std::fs::write(
+        &table,
+        format!(
+            r#"[
+                {{ "type": "account", "identifier": "{}", "trust": 30 }},
+                {{ "type": "bank", "identifier": "{}", "trust": 60 }}
+            ]"#,
+            acc.account_number(),
+            Account::new().account_number()
+        ),
+    )
+    .unwrap();
+
+    let signing_key = network.bank_signer().signing_key();
+    let output = tnb(&[
+        "trust",
+        "--signing-key",
+        signing_key.as_str(),
+        "--node",
+        network.bank_url(),
+        table.to_str().unwrap(),
+    ]);
+    std::fs::remove_file(&table).unwrap();
+
+    // the unknown bank fails but the account's trust is still updated
+    assert!(!output.status.success());
+
+    let output: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(output["updates"][0]["type"], "account");
+    assert_eq!(output["updates"][0]["stored_trust"], "30.00");
+    assert!(output["updates"][1]["error"].is_string());
+}